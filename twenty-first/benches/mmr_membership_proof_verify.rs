@@ -0,0 +1,49 @@
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::RngCore;
+use rand::SeedableRng;
+
+use twenty_first::shared_math::tip5::Tip5;
+use twenty_first::test_shared::mmr::get_rustyleveldb_ammr_from_digests;
+use twenty_first::util_types::algebraic_hasher::AlgebraicHasher;
+use twenty_first::util_types::mmr::archival_mmr::ArchivalMmr;
+use twenty_first::util_types::mmr::mmr_trait::Mmr;
+use twenty_first::util_types::storage_vec::RustyLevelDbVec;
+
+fn mmr_membership_proof_verify(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let leaf_count = 1 << 16;
+    let leaves = (0..leaf_count).map(|_| rng.next_u64()).collect_vec();
+    let leaf_digests = leaves.iter().map(Tip5::hash).collect_vec();
+    let archival_mmr: ArchivalMmr<Tip5, RustyLevelDbVec<_>> =
+        get_rustyleveldb_ammr_from_digests(leaf_digests.clone());
+    let peaks = archival_mmr.get_peaks();
+
+    let num_proofs = 10_000;
+    let queried_indices = (0..num_proofs)
+        .map(|_| rng.gen_range(0..leaf_count))
+        .collect_vec();
+    let membership_proofs = queried_indices
+        .iter()
+        .map(|&i| archival_mmr.prove_membership(i as u64).0)
+        .collect_vec();
+
+    let mut group = c.benchmark_group("mmr_membership_proof_verify");
+    group.bench_function(BenchmarkId::new("verify_many", num_proofs), |bencher| {
+        bencher.iter(|| {
+            for (membership_proof, &leaf_index) in membership_proofs.iter().zip_eq(&queried_indices)
+            {
+                membership_proof.verify(&peaks, &leaf_digests[leaf_index], leaf_count as u64);
+            }
+        });
+    });
+}
+
+criterion_group!(benches, mmr_membership_proof_verify);
+criterion_main!(benches);
@@ -3,12 +3,14 @@ pub mod bfield_codec;
 pub mod circuit;
 pub mod digest;
 pub mod fips202;
+pub mod hash_parameters;
 pub mod lattice;
 pub mod mds;
 pub mod mpolynomial;
 pub mod ntt;
 pub mod other;
 pub mod polynomial;
+pub mod rescue_prime_optimized;
 pub mod tip5;
 pub mod traits;
 pub mod x_field_element;
@@ -0,0 +1,140 @@
+//! Utilities for deriving the round constants and MDS matrix of a Rescue/Poseidon-style
+//! permutation from a short seed, together with the security checks a hand-picked instantiation
+//! should pass, so that alternative widths and round counts do not require hard-coded tables.
+//!
+//! Constants are derived in the style of the constants of [`Tip5`](crate::shared_math::tip5::Tip5):
+//! by hashing `seed || index` with BLAKE3 and reducing the digest modulo the field's
+//! characteristic, in the spirit of a Grain LFSR-based generator. Here the index is encoded as 8
+//! little-endian bytes where Tip5 used a single byte, so the seed `"Tip5"` does not reproduce
+//! Tip5's own round constants.
+
+use itertools::Itertools;
+use num_traits::Zero;
+
+use crate::shared_math::b_field_element::BFieldElement;
+use crate::shared_math::traits::Inverse;
+
+/// Derive `num_constants` round constants from `seed`.
+///
+/// Constant `i` is `blake3(seed || i.to_le_bytes()) mod p`, truncated to the first 16 bytes of
+/// the digest before reduction.
+pub fn round_constants_from_seed(seed: &str, num_constants: usize) -> Vec<BFieldElement> {
+    let to_int = |bytes: &[u8]| {
+        bytes
+            .iter()
+            .take(16)
+            .enumerate()
+            .map(|(i, b)| (*b as u128) << (8 * i))
+            .sum::<u128>()
+    };
+
+    (0..num_constants as u64)
+        .map(|i| [seed.as_bytes(), &i.to_le_bytes()].concat())
+        .map(|bytes| blake3::hash(&bytes))
+        .map(|hash| *hash.as_bytes())
+        .map(|bytes| to_int(&bytes))
+        .map(|i| (i % BFieldElement::P as u128) as u64)
+        .map(BFieldElement::from_raw_u64)
+        .collect()
+}
+
+/// Derive the first column of a candidate `width`×`width` circulant MDS matrix from `seed`,
+/// re-deriving with an incrementing counter until [`is_invertible_circulant`] accepts it.
+///
+/// Returns the accepted first column together with the number of candidates that were rejected.
+pub fn mds_first_column_from_seed(seed: &str, width: usize) -> (Vec<BFieldElement>, usize) {
+    for attempt in 0u64.. {
+        let candidate_seed = format!("{seed}-mds-{attempt}");
+        let column = round_constants_from_seed(&candidate_seed, width);
+        if is_invertible_circulant(&column) {
+            return (column, attempt as usize);
+        }
+    }
+    unreachable!("the field has more than u64::MAX candidate columns")
+}
+
+/// Build the full `width`×`width` circulant matrix whose first column is `first_column`.
+///
+/// Row `i`, column `j` holds `first_column[(i + width - j) % width]`, matching the convention
+/// used by [`Tip5`](crate::shared_math::tip5::Tip5)'s MDS matrix.
+pub(crate) fn circulant_matrix(first_column: &[BFieldElement]) -> Vec<Vec<BFieldElement>> {
+    let width = first_column.len();
+    (0..width)
+        .map(|i| {
+            (0..width)
+                .map(|j| first_column[(i + width - j) % width])
+                .collect_vec()
+        })
+        .collect_vec()
+}
+
+/// Determine whether the circulant matrix generated by `first_column` is invertible over the
+/// field, by reducing it to row-echelon form with partial pivoting and checking that every pivot
+/// is nonzero. An MDS candidate that fails this check has a nontrivial kernel and would not
+/// provide full diffusion.
+pub fn is_invertible_circulant(first_column: &[BFieldElement]) -> bool {
+    let width = first_column.len();
+    let mut matrix = circulant_matrix(first_column);
+
+    for pivot in 0..width {
+        let Some(pivot_row) = (pivot..width).find(|&row| !matrix[row][pivot].is_zero()) else {
+            return false;
+        };
+        matrix.swap(pivot, pivot_row);
+
+        let pivot_inverse = matrix[pivot][pivot].inverse();
+        let pivot_row_values = matrix[pivot].clone();
+        for row in matrix.iter_mut().skip(pivot + 1) {
+            let factor = row[pivot] * pivot_inverse;
+            if !factor.is_zero() {
+                for (cell, pivot_value) in row.iter_mut().zip(pivot_row_values.iter()).skip(pivot) {
+                    *cell -= factor * *pivot_value;
+                }
+            }
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod hash_parameters_tests {
+    use crate::shared_math::tip5::MDS_MATRIX_FIRST_COLUMN;
+
+    use super::*;
+
+    #[test]
+    fn round_constants_are_deterministic() {
+        let a = round_constants_from_seed("test-seed", 30);
+        let b = round_constants_from_seed("test-seed", 30);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn different_seeds_give_different_constants() {
+        let a = round_constants_from_seed("seed-a", 10);
+        let b = round_constants_from_seed("seed-b", 10);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn tip5_mds_matrix_is_invertible() {
+        let column = MDS_MATRIX_FIRST_COLUMN
+            .into_iter()
+            .map(|c| BFieldElement::new(c.rem_euclid(BFieldElement::P as i64) as u64))
+            .collect_vec();
+        assert!(is_invertible_circulant(&column));
+    }
+
+    #[test]
+    fn all_zero_column_is_not_invertible() {
+        let column = vec![BFieldElement::zero(); 16];
+        assert!(!is_invertible_circulant(&column));
+    }
+
+    #[test]
+    fn generated_mds_column_is_invertible() {
+        let (column, _attempts) = mds_first_column_from_seed("test-instance", 16);
+        assert!(is_invertible_circulant(&column));
+    }
+}
@@ -0,0 +1,242 @@
+//! Rescue-Prime Optimized (RPO): a Rescue-style sponge permutation implementing the same
+//! [`SpongeHasher`]/[`AlgebraicHasher`] traits as [`Tip5`](crate::shared_math::tip5::Tip5), with
+//! its own round structure and constants, so that users interoperating with Miden-style systems
+//! have an RPO-shaped hasher available without pulling in a separate crate.
+//!
+//! Unlike [`Tip5`](crate::shared_math::tip5::Tip5), which splits its S-box layer between a
+//! byte-lookup half and a power-map half, RPO applies a *full* Rescue round to every state
+//! element: a forward power map, an MDS mixing, an addition of round constants, an inverse power
+//! map, and a second MDS mixing and addition of round constants. Doing the power map and its
+//! inverse in the same round is what makes Rescue-style permutations equally cheap to arithmetize
+//! in both directions.
+//!
+//! The round constants and MDS matrix below are derived deterministically from a seed via
+//! [`hash_parameters`](crate::shared_math::hash_parameters), the same mechanism used to evaluate
+//! candidate parameterizations of Tip5. They are *not* the constants published for Miden's
+//! `Rpo256`, so digests produced here will not match `miden-crypto` bit-for-bit; reproducing that
+//! exactly would require importing Miden's published constant tables, which this crate does not
+//! vendor.
+
+use bfieldcodec_derive::BFieldCodec;
+use get_size::GetSize;
+use num_traits::Zero;
+use serde::{Deserialize, Serialize};
+
+use crate::shared_math::b_field_element::{BFieldElement, BFIELD_ONE, BFIELD_ZERO};
+use crate::shared_math::digest::{Digest, DIGEST_LENGTH};
+use crate::shared_math::hash_parameters::{
+    circulant_matrix, mds_first_column_from_seed, round_constants_from_seed,
+};
+use crate::shared_math::traits::ModPowU64;
+use crate::util_types::algebraic_hasher::{AlgebraicHasher, Domain, SpongeHasher};
+
+pub const STATE_SIZE: usize = 16;
+pub const CAPACITY: usize = 6;
+pub const RATE: usize = 10;
+pub const NUM_ROUNDS: usize = 8;
+
+/// The exponent of RPO's forward power-map S-box. `gcd(7, p - 1) = 1`, so `x -> x^7` is a
+/// bijection on the field's nonzero elements.
+const ALPHA: u64 = 7;
+
+/// `7⁻¹ mod (p - 1)`: the exponent inverting the forward power map. Identical to the value used
+/// by [`Tip5`](crate::shared_math::tip5::Tip5)'s inverse S-box, since it depends only on the
+/// field's characteristic, not on the permutation built over it.
+const ALPHA_INVERSE: u64 = 10540996611094048183;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RescuePrimeOptimizedState {
+    pub state: [BFieldElement; STATE_SIZE],
+}
+
+impl RescuePrimeOptimizedState {
+    #[inline]
+    pub fn new(domain: Domain) -> Self {
+        let mut state = [BFIELD_ZERO; STATE_SIZE];
+
+        if domain == Domain::FixedLength {
+            state[RATE..STATE_SIZE].fill(BFIELD_ONE);
+        }
+
+        Self { state }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq, GetSize, BFieldCodec)]
+pub struct RescuePrimeOptimized {}
+
+impl RescuePrimeOptimized {
+    /// The round constants for both half-rounds of every round, derived once from a fixed seed
+    /// and cached. Laid out as `NUM_ROUNDS` pairs of `STATE_SIZE`-element blocks: the constants
+    /// added after the forward S-box, then the constants added after the inverse S-box.
+    fn round_constants() -> &'static [BFieldElement] {
+        static CONSTANTS: std::sync::OnceLock<Vec<BFieldElement>> = std::sync::OnceLock::new();
+        CONSTANTS.get_or_init(|| {
+            round_constants_from_seed(
+                "twenty-first/rescue-prime-optimized/round-constants",
+                2 * NUM_ROUNDS * STATE_SIZE,
+            )
+        })
+    }
+
+    /// The dense `STATE_SIZE`×`STATE_SIZE` MDS matrix, derived once from a fixed seed and cached.
+    fn mds_matrix() -> &'static [[BFieldElement; STATE_SIZE]; STATE_SIZE] {
+        static MATRIX: std::sync::OnceLock<[[BFieldElement; STATE_SIZE]; STATE_SIZE]> =
+            std::sync::OnceLock::new();
+        MATRIX.get_or_init(|| {
+            let (first_column, _rejected_candidates) =
+                mds_first_column_from_seed("twenty-first/rescue-prime-optimized/mds", STATE_SIZE);
+            let mut matrix = [[BFieldElement::zero(); STATE_SIZE]; STATE_SIZE];
+            for (row, generated_row) in matrix.iter_mut().zip(circulant_matrix(&first_column)) {
+                *row = generated_row.try_into().unwrap();
+            }
+            matrix
+        })
+    }
+
+    #[inline(always)]
+    fn apply_mds(state: &mut [BFieldElement; STATE_SIZE]) {
+        let matrix = Self::mds_matrix();
+        let mut result = [BFieldElement::zero(); STATE_SIZE];
+        for (entry, row) in result.iter_mut().zip(matrix.iter()) {
+            *entry = row.iter().zip(state.iter()).map(|(&m, &s)| m * s).sum();
+        }
+        *state = result;
+    }
+
+    #[inline(always)]
+    fn add_round_constants(state: &mut [BFieldElement; STATE_SIZE], half_round_index: usize) {
+        let constants = &Self::round_constants()[half_round_index * STATE_SIZE..][..STATE_SIZE];
+        for (s, &c) in state.iter_mut().zip(constants) {
+            *s += c;
+        }
+    }
+
+    #[inline(always)]
+    fn round(state: &mut [BFieldElement; STATE_SIZE], round_index: usize) {
+        for s in state.iter_mut() {
+            *s = s.mod_pow_u64(ALPHA);
+        }
+        Self::apply_mds(state);
+        Self::add_round_constants(state, 2 * round_index);
+
+        for s in state.iter_mut() {
+            *s = s.mod_pow_u64(ALPHA_INVERSE);
+        }
+        Self::apply_mds(state);
+        Self::add_round_constants(state, 2 * round_index + 1);
+    }
+
+    /// Apply the RPO permutation in place on a fixed-size state.
+    #[inline(always)]
+    pub fn permute(state: &mut [BFieldElement; STATE_SIZE]) {
+        for i in 0..NUM_ROUNDS {
+            Self::round(state, i);
+        }
+    }
+
+    #[inline(always)]
+    fn permutation(sponge: &mut RescuePrimeOptimizedState) {
+        Self::permute(&mut sponge.state);
+    }
+
+    /// Hash [`RATE`] elements, or two digests. There is no padding because the input length is
+    /// fixed.
+    pub fn hash_10(input: &[BFieldElement; RATE]) -> [BFieldElement; DIGEST_LENGTH] {
+        let mut sponge = RescuePrimeOptimizedState::new(Domain::FixedLength);
+        sponge.state[..RATE].copy_from_slice(input);
+        Self::permute(&mut sponge.state);
+        sponge.state[..DIGEST_LENGTH].try_into().unwrap()
+    }
+}
+
+impl AlgebraicHasher for RescuePrimeOptimized {
+    /// Hash two digests into one, using the fixed-arity [`RescuePrimeOptimized::hash_10`] fast
+    /// path directly, exactly as [`Tip5::hash_pair`](crate::shared_math::tip5::Tip5::hash_pair)
+    /// does for its own permutation.
+    fn hash_pair(left: &Digest, right: &Digest) -> Digest {
+        let mut input = [BFIELD_ZERO; RATE];
+        input[..DIGEST_LENGTH].copy_from_slice(&left.values());
+        input[DIGEST_LENGTH..].copy_from_slice(&right.values());
+        Digest::new(Self::hash_10(&input))
+    }
+}
+
+impl SpongeHasher for RescuePrimeOptimized {
+    const RATE: usize = RATE;
+    type SpongeState = RescuePrimeOptimizedState;
+
+    fn init() -> Self::SpongeState {
+        RescuePrimeOptimizedState::new(Domain::VariableLength)
+    }
+
+    fn absorb(sponge: &mut Self::SpongeState, input: &[BFieldElement; RATE]) {
+        sponge.state[..RATE].copy_from_slice(input);
+        Self::permutation(sponge);
+    }
+
+    fn squeeze(sponge: &mut Self::SpongeState) -> [BFieldElement; RATE] {
+        let produce: [BFieldElement; RATE] = sponge.state[..RATE].try_into().unwrap();
+        Self::permutation(sponge);
+        produce
+    }
+}
+
+#[cfg(test)]
+mod rescue_prime_optimized_tests {
+    use crate::shared_math::other::random_elements;
+    use crate::util_types::algebraic_hasher::AlgebraicHasher;
+
+    use super::*;
+
+    #[test]
+    fn permutation_is_deterministic() {
+        let initial_state: [BFieldElement; STATE_SIZE] =
+            random_elements(STATE_SIZE).try_into().unwrap();
+
+        let mut a = initial_state;
+        RescuePrimeOptimized::permute(&mut a);
+        let mut b = initial_state;
+        RescuePrimeOptimized::permute(&mut b);
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn permutation_is_not_identity() {
+        let mut state: [BFieldElement; STATE_SIZE] =
+            random_elements(STATE_SIZE).try_into().unwrap();
+        let original = state;
+
+        RescuePrimeOptimized::permute(&mut state);
+
+        assert_ne!(original, state);
+    }
+
+    #[test]
+    fn hash_pair_uses_hash_10_fast_path() {
+        let left = Digest::new(random_elements(DIGEST_LENGTH).try_into().unwrap());
+        let right = Digest::new(random_elements(DIGEST_LENGTH).try_into().unwrap());
+
+        let mut input = [BFieldElement::zero(); RATE];
+        input[..DIGEST_LENGTH].copy_from_slice(&left.values());
+        input[DIGEST_LENGTH..].copy_from_slice(&right.values());
+
+        let expected = Digest::new(RescuePrimeOptimized::hash_10(&input));
+        assert_eq!(expected, RescuePrimeOptimized::hash_pair(&left, &right));
+    }
+
+    #[test]
+    fn hash_varlen_of_empty_input_is_deterministic() {
+        let a = RescuePrimeOptimized::hash_varlen(&[]);
+        let b = RescuePrimeOptimized::hash_varlen(&[]);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn different_inputs_hash_differently() {
+        let a = RescuePrimeOptimized::hash_varlen(&random_elements(12));
+        let b = RescuePrimeOptimized::hash_varlen(&random_elements(12));
+        assert_ne!(a, b);
+    }
+}
@@ -10,6 +10,7 @@ pub use crate::shared_math::digest::{Digest, DIGEST_LENGTH};
 use crate::util_types::algebraic_hasher::{AlgebraicHasher, Domain, SpongeHasher};
 
 use crate::shared_math::mds::generated_function;
+use crate::shared_math::traits::{Inverse, ModPowU64};
 use crate::shared_math::x_field_element::XFieldElement;
 use crate::shared_math::x_field_element::EXTENSION_DEGREE;
 
@@ -161,6 +162,23 @@ pub const MDS_MATRIX_FIRST_COLUMN: [i64; STATE_SIZE] = [
     26798, 17845,
 ];
 
+/// The inverse of [`LOOKUP_TABLE`] as a permutation of bytes; used by
+/// [`Tip5::inverse_permute`] to undo the split-and-lookup half of the S-box layer.
+pub const INVERSE_LOOKUP_TABLE: [u8; 256] = {
+    let mut inverse = [0u8; 256];
+    let mut i = 0;
+    while i < 256 {
+        inverse[LOOKUP_TABLE[i] as usize] = i as u8;
+        i += 1;
+    }
+    inverse
+};
+
+/// `7⁻¹ mod (p - 1)`: the exponent that inverts the power map `x -> x^7` used by the power half
+/// of [`Tip5::sbox_layer`]. Since `gcd(7, p - 1) = 1`, `x -> x^7` is a bijection on the field's
+/// nonzero elements, and this is its unique inverse exponent.
+const INVERSE_POWER_MAP_EXPONENT: u64 = 10540996611094048183;
+
 impl Tip5 {
     #[inline]
     pub const fn offset_fermat_cube_map(x: u16) -> u16 {
@@ -539,40 +557,190 @@ impl Tip5 {
     }
 
     #[inline(always)]
-    fn round(sponge: &mut Tip5State, round_index: usize) {
-        Self::sbox_layer(&mut sponge.state);
+    fn round(state: &mut [BFieldElement; STATE_SIZE], round_index: usize) {
+        Self::sbox_layer(state);
 
-        // Self::mds_cyclomul(&mut sponge.state);
-        Self::mds_generated(&mut sponge.state);
+        // Self::mds_cyclomul(state);
+        Self::mds_generated(state);
 
         for i in 0..STATE_SIZE {
-            sponge.state[i] += ROUND_CONSTANTS[round_index * STATE_SIZE + i];
+            state[i] += ROUND_CONSTANTS[round_index * STATE_SIZE + i];
         }
     }
 
-    // permutation
+    /// Apply the Tip5 permutation in place on a fixed-size state, without any heap allocation.
     #[inline(always)]
-    fn permutation(sponge: &mut Tip5State) {
+    pub fn permute(state: &mut [BFieldElement; STATE_SIZE]) {
         for i in 0..NUM_ROUNDS {
-            Self::round(sponge, i);
+            Self::round(state, i);
+        }
+    }
+
+    /// The full, dense MDS matrix corresponding to [`MDS_MATRIX_FIRST_COLUMN`], computed once
+    /// and cached. [`Self::inverse_mds`] needs the actual inverse matrix, unlike the forward
+    /// direction's [`Self::mds_generated`], which only ever multiplies by this matrix and can
+    /// therefore use a specialized, allocation-free algorithm.
+    fn mds_matrix() -> &'static [[BFieldElement; STATE_SIZE]; STATE_SIZE] {
+        static MATRIX: std::sync::OnceLock<[[BFieldElement; STATE_SIZE]; STATE_SIZE]> =
+            std::sync::OnceLock::new();
+        MATRIX.get_or_init(|| {
+            let mut matrix = [[BFieldElement::zero(); STATE_SIZE]; STATE_SIZE];
+            for (i, row) in matrix.iter_mut().enumerate() {
+                for (j, entry) in row.iter_mut().enumerate() {
+                    let first_column_entry =
+                        MDS_MATRIX_FIRST_COLUMN[(i + STATE_SIZE - j) % STATE_SIZE];
+                    *entry = BFieldElement::new(
+                        first_column_entry.rem_euclid(BFieldElement::P as i64) as u64,
+                    );
+                }
+            }
+            matrix
+        })
+    }
+
+    /// The inverse of [`Self::mds_matrix`], computed once by Gauss-Jordan elimination and
+    /// cached.
+    fn inverse_mds_matrix() -> &'static [[BFieldElement; STATE_SIZE]; STATE_SIZE] {
+        static INVERSE: std::sync::OnceLock<[[BFieldElement; STATE_SIZE]; STATE_SIZE]> =
+            std::sync::OnceLock::new();
+        INVERSE.get_or_init(|| Self::invert_matrix(Self::mds_matrix()))
+    }
+
+    /// Invert a square matrix over the field by Gauss-Jordan elimination on the augmented
+    /// matrix `[matrix | identity]`. Panics if `matrix` is singular.
+    fn invert_matrix(
+        matrix: &[[BFieldElement; STATE_SIZE]; STATE_SIZE],
+    ) -> [[BFieldElement; STATE_SIZE]; STATE_SIZE] {
+        let n = STATE_SIZE;
+        let mut augmented: Vec<Vec<BFieldElement>> = (0..n)
+            .map(|i| {
+                let mut row = matrix[i].to_vec();
+                row.extend((0..n).map(|j| BFieldElement::new((i == j) as u64)));
+                row
+            })
+            .collect();
+
+        for pivot in 0..n {
+            let pivot_row = (pivot..n)
+                .find(|&row| !augmented[row][pivot].is_zero())
+                .expect("MDS matrix must be invertible");
+            augmented.swap(pivot, pivot_row);
+
+            let pivot_inverse = augmented[pivot][pivot].inverse();
+            for entry in augmented[pivot].iter_mut() {
+                *entry *= pivot_inverse;
+            }
+
+            for row in 0..n {
+                if row == pivot {
+                    continue;
+                }
+                let factor = augmented[row][pivot];
+                if !factor.is_zero() {
+                    let pivot_row_values = augmented[pivot].clone();
+                    for (entry, pivot_entry) in
+                        augmented[row].iter_mut().zip(pivot_row_values.iter())
+                    {
+                        *entry -= factor * *pivot_entry;
+                    }
+                }
+            }
+        }
+
+        let mut inverse = [[BFieldElement::zero(); STATE_SIZE]; STATE_SIZE];
+        for (i, row) in inverse.iter_mut().enumerate() {
+            row.copy_from_slice(&augmented[i][n..]);
         }
+        inverse
     }
 
-    /// Functionally equivalent to [`permutation`](Self::permutation). Returns the trace of
-    /// applying the permutation; that is, the initial state of the sponge as well as its state
-    /// after each round.
+    /// Undo [`Self::mds_generated`] by multiplying with the inverse MDS matrix directly; unlike
+    /// the forward direction, this is not performance-critical.
+    #[inline]
+    fn inverse_mds(state: &mut [BFieldElement; STATE_SIZE]) {
+        let inverse = Self::inverse_mds_matrix();
+        let mut result = [BFieldElement::zero(); STATE_SIZE];
+        for (i, row) in inverse.iter().enumerate() {
+            result[i] = row.iter().zip(state.iter()).map(|(&a, &b)| a * b).sum();
+        }
+        *state = result;
+    }
+
+    /// Undo [`Self::sbox_layer`]: apply the inverse byte lookup to the first
+    /// [`NUM_SPLIT_AND_LOOKUP`] elements, and raise the remaining elements to
+    /// [`INVERSE_POWER_MAP_EXPONENT`], which undoes the `x -> x^7` power map.
+    #[inline]
+    fn inverse_sbox_layer(state: &mut [BFieldElement; STATE_SIZE]) {
+        for element in state.iter_mut().take(NUM_SPLIT_AND_LOOKUP) {
+            let mut bytes = element.raw_bytes();
+            for byte in bytes.iter_mut() {
+                *byte = INVERSE_LOOKUP_TABLE[*byte as usize];
+            }
+            *element = BFieldElement::from_raw_bytes(&bytes);
+        }
+
+        for element in state.iter_mut().skip(NUM_SPLIT_AND_LOOKUP) {
+            *element = element.mod_pow_u64(INVERSE_POWER_MAP_EXPONENT);
+        }
+    }
+
+    /// Undo one round of [`Self::round`], given the state right after that round completed.
+    #[inline]
+    fn inverse_round(state: &mut [BFieldElement; STATE_SIZE], round_index: usize) {
+        for i in 0..STATE_SIZE {
+            state[i] -= ROUND_CONSTANTS[round_index * STATE_SIZE + i];
+        }
+        Self::inverse_mds(state);
+        Self::inverse_sbox_layer(state);
+    }
+
+    /// Apply the inverse of [`Self::permute`] in place: `inverse_permute(permute(state)) ==
+    /// state` for every `state`. Enables using Tip5 as a public permutation in constructions,
+    /// such as an Even-Mansour block cipher, that need to run it in both directions.
+    pub fn inverse_permute(state: &mut [BFieldElement; STATE_SIZE]) {
+        for round_index in (0..NUM_ROUNDS).rev() {
+            Self::inverse_round(state, round_index);
+        }
+    }
+
+    // permutation
+    #[inline(always)]
+    fn permutation(sponge: &mut Tip5State) {
+        Self::permute(&mut sponge.state);
+    }
+
+    /// Functionally equivalent to [`permute`](Self::permute). Returns the trace of
+    /// applying the permutation; that is, the initial state as well as the state after each
+    /// round.
     pub fn trace(sponge: &mut Tip5State) -> [[BFieldElement; STATE_SIZE]; 1 + NUM_ROUNDS] {
         let mut trace = [[BFIELD_ZERO; STATE_SIZE]; 1 + NUM_ROUNDS];
 
         trace[0] = sponge.state;
         for i in 0..NUM_ROUNDS {
-            Self::round(sponge, i);
+            Self::round(&mut sponge.state, i);
             trace[1 + i] = sponge.state;
         }
 
         trace
     }
 
+    /// Functionally equivalent to [`permute`](Self::permute), but returns every intermediate
+    /// state of the permutation: the initial state and the state after each round, in a `Vec`.
+    ///
+    /// Lets a STARK hash coprocessor table be filled directly from this trace instead of
+    /// duplicating the round logic.
+    pub fn permutation_trace(
+        state: &mut [BFieldElement; STATE_SIZE],
+    ) -> Vec<[BFieldElement; STATE_SIZE]> {
+        let mut trace = Vec::with_capacity(1 + NUM_ROUNDS);
+        trace.push(*state);
+        for i in 0..NUM_ROUNDS {
+            Self::round(state, i);
+            trace.push(*state);
+        }
+        trace
+    }
+
     /// hash_10
     /// Hash 10 elements, or two digests. There is no padding because
     /// the input length is fixed.
@@ -583,14 +751,64 @@ impl Tip5 {
         sponge.state[..10].copy_from_slice(input);
 
         // apply permutation
-        Self::permutation(&mut sponge);
+        Self::permute(&mut sponge.state);
 
         // squeeze once
         sponge.state[..DIGEST_LENGTH].try_into().unwrap()
     }
 }
 
+/// A single-key Even-Mansour block cipher built on the public [`Tip5`] permutation: key
+/// whitening, then the permutation, then key whitening again. Since [`Tip5::permute`] and
+/// [`Tip5::inverse_permute`] are exact inverses, this lets the same audited permutation double
+/// as field-native symmetric encryption for stored witnesses, instead of only hashing.
+///
+/// As with any Even-Mansour cipher, security relies on the permutation behaving like a random
+/// permutation and holds up to the birthday bound on the state size; it is not intended to
+/// replace a dedicated, well-analyzed cipher for high-value data.
+pub struct Tip5Cipher;
+
+impl Tip5Cipher {
+    /// Encrypt one `STATE_SIZE`-wide block under `key`.
+    pub fn encrypt_block(
+        key: &[BFieldElement; STATE_SIZE],
+        block: &[BFieldElement; STATE_SIZE],
+    ) -> [BFieldElement; STATE_SIZE] {
+        let mut state = *block;
+        for i in 0..STATE_SIZE {
+            state[i] += key[i];
+        }
+        Tip5::permute(&mut state);
+        for i in 0..STATE_SIZE {
+            state[i] += key[i];
+        }
+        state
+    }
+
+    /// Decrypt one `STATE_SIZE`-wide block under `key`. Inverse of
+    /// [`Tip5Cipher::encrypt_block`].
+    pub fn decrypt_block(
+        key: &[BFieldElement; STATE_SIZE],
+        block: &[BFieldElement; STATE_SIZE],
+    ) -> [BFieldElement; STATE_SIZE] {
+        let mut state = *block;
+        for i in 0..STATE_SIZE {
+            state[i] -= key[i];
+        }
+        Tip5::inverse_permute(&mut state);
+        for i in 0..STATE_SIZE {
+            state[i] -= key[i];
+        }
+        state
+    }
+}
+
 impl AlgebraicHasher for Tip5 {
+    /// Hash two digests into one, using the fixed-arity [`Tip5::hash_10`] fast path directly.
+    ///
+    /// This is the hottest call site in the crate (every internal Merkle tree node goes through
+    /// it), so it must not pay for the padding and length bookkeeping that
+    /// [`AlgebraicHasher::hash_varlen`] needs for arbitrary-length input.
     fn hash_pair(left: &Digest, right: &Digest) -> Digest {
         let mut input = [BFIELD_ZERO; 10];
         input[..DIGEST_LENGTH].copy_from_slice(&left.values());
@@ -665,9 +883,11 @@ mod tip5_tests {
     use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 
     use crate::shared_math::b_field_element::BFieldElement;
+    use crate::shared_math::digest::Digest;
     use crate::shared_math::digest::DIGEST_LENGTH;
     use crate::shared_math::other::random_elements;
     use crate::shared_math::tip5::Tip5;
+    use crate::shared_math::tip5::Tip5Cipher;
     use crate::shared_math::tip5::LOOKUP_TABLE;
     use crate::shared_math::tip5::NUM_ROUNDS;
     use crate::shared_math::tip5::ROUND_CONSTANTS;
@@ -829,6 +1049,60 @@ mod tip5_tests {
         println!("agreement with low-degree function: {equal_count}");
     }
 
+    #[test]
+    fn permutation_trace_matches_permute() {
+        let initial_state: [BFieldElement; STATE_SIZE] =
+            random_elements(STATE_SIZE).try_into().unwrap();
+
+        let mut via_trace = initial_state;
+        let trace = Tip5::permutation_trace(&mut via_trace);
+
+        let mut via_permute = initial_state;
+        Tip5::permute(&mut via_permute);
+
+        assert_eq!(1 + NUM_ROUNDS, trace.len());
+        assert_eq!(initial_state, trace[0]);
+        assert_eq!(via_permute, *trace.last().unwrap());
+        assert_eq!(via_permute, via_trace);
+    }
+
+    #[test]
+    fn inverse_permute_undoes_permute() {
+        let original: [BFieldElement; STATE_SIZE] = random_elements(STATE_SIZE).try_into().unwrap();
+
+        let mut state = original;
+        Tip5::permute(&mut state);
+        Tip5::inverse_permute(&mut state);
+
+        assert_eq!(original, state);
+    }
+
+    #[test]
+    fn tip5_cipher_decrypt_undoes_encrypt() {
+        let key: [BFieldElement; STATE_SIZE] = random_elements(STATE_SIZE).try_into().unwrap();
+        let plaintext: [BFieldElement; STATE_SIZE] =
+            random_elements(STATE_SIZE).try_into().unwrap();
+
+        let ciphertext = Tip5Cipher::encrypt_block(&key, &plaintext);
+        assert_ne!(plaintext, ciphertext);
+
+        let decrypted = Tip5Cipher::decrypt_block(&key, &ciphertext);
+        assert_eq!(plaintext, decrypted);
+    }
+
+    #[test]
+    fn hash_pair_uses_hash_10_fast_path() {
+        let left = Digest::new(random_elements(DIGEST_LENGTH).try_into().unwrap());
+        let right = Digest::new(random_elements(DIGEST_LENGTH).try_into().unwrap());
+
+        let mut input = [BFieldElement::zero(); RATE];
+        input[..DIGEST_LENGTH].copy_from_slice(&left.values());
+        input[DIGEST_LENGTH..].copy_from_slice(&right.values());
+
+        let expected = Digest::new(Tip5::hash_10(&input));
+        assert_eq!(expected, Tip5::hash_pair(&left, &right));
+    }
+
     #[test]
     fn hash10_test_vectors() {
         let mut preimage = [BFieldElement::zero(); RATE];
@@ -3,7 +3,7 @@ use std::sync::{Arc, Mutex};
 use rusty_leveldb::DB;
 
 use crate::shared_math::digest::Digest;
-use crate::util_types::storage_vec::RustyLevelDbVec;
+use crate::util_types::storage_vec::{OrdinaryVec, RustyLevelDbVec};
 use crate::util_types::{algebraic_hasher::AlgebraicHasher, mmr::archival_mmr::ArchivalMmr};
 
 /// Return an empty in-memory archival MMR for testing purposes.
@@ -32,3 +32,24 @@ where
 
     ammr
 }
+
+/// Return an empty, purely in-memory archival MMR for testing purposes: the
+/// [`OrdinaryVec`]-backed counterpart to [`get_empty_rustyleveldb_ammr`], for tests that want to
+/// exercise [`ArchivalMmr`]'s logic without paying for a LevelDB instance.
+pub fn get_empty_ordinary_vec_ammr<H: AlgebraicHasher>() -> ArchivalMmr<H, OrdinaryVec<Digest>> {
+    ArchivalMmr::new(OrdinaryVec::default())
+}
+
+pub fn get_ordinary_vec_ammr_from_digests<H>(
+    digests: Vec<Digest>,
+) -> ArchivalMmr<H, OrdinaryVec<Digest>>
+where
+    H: AlgebraicHasher,
+{
+    let mut ammr = get_empty_ordinary_vec_ammr();
+    for digest in digests {
+        ammr.append_raw(digest);
+    }
+
+    ammr
+}
@@ -1,13 +1,40 @@
 pub mod algebraic_hasher;
+pub mod async_database_vector;
+pub mod batch_hasher;
 pub mod blake3_wrapper;
+pub mod checksummed_codec;
+pub mod compact_authentication_path;
 pub mod database_array;
+pub mod database_map;
+pub mod database_queue;
+pub mod database_stack;
 pub mod database_vector;
+pub mod database_vector_codec;
+pub mod database_vector_index;
 pub mod emojihash_trait;
+pub mod incremental_merkle_tree;
+pub mod lru_cache;
+pub mod merkle_forest;
 pub mod merkle_tree;
 pub mod merkle_tree_maker;
+#[cfg(feature = "mmap-backend")]
+pub mod mmap_backend;
 pub mod mmr;
+pub mod mutable_merkle_tree;
+pub mod n_ary_merkle_tree;
+pub mod pluggable_merkle_tree;
+pub mod prefixed_db;
 pub mod proof_stream_typed;
+pub mod safe_sponge;
+pub mod salted_merkle_tree;
 pub mod shared;
+pub mod shared_database_vector;
+pub mod sparse_merkle_tree;
+pub mod sponge_prng;
+pub mod storage_backend;
 pub mod storage_schema;
 pub mod storage_vec;
+pub mod transaction;
+pub mod transcript;
 pub mod tree_m_ary;
+pub mod typed_merkle_tree;
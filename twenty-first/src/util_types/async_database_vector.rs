@@ -0,0 +1,171 @@
+use super::database_vector::{DatabaseVector, DatabaseVectorError};
+use super::database_vector_codec::{BincodeCodec, ValueCodec};
+use super::shared_database_vector::SharedDatabaseVector;
+
+type IndexType = u64;
+
+/// An async handle to a [`DatabaseVector`], obtained via [`DatabaseVector::into_async`].
+///
+/// `rusty_leveldb` is entirely blocking, so every method here runs the equivalent
+/// [`SharedDatabaseVector`] call on tokio's blocking thread pool via
+/// [`tokio::task::spawn_blocking`] and awaits the result, instead of blocking whichever executor
+/// thread called it. Like `SharedDatabaseVector`, this handle is cheap to clone and every clone
+/// talks to the same underlying vector, serialized through the same mutex.
+pub struct AsyncDatabaseVector<T, C: ValueCodec<T> = BincodeCodec> {
+    inner: SharedDatabaseVector<T, C>,
+}
+
+impl<T, C: ValueCodec<T>> Clone for AsyncDatabaseVector<T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T, C> AsyncDatabaseVector<T, C>
+where
+    T: Clone + Send + 'static,
+    C: ValueCodec<T> + Send + 'static,
+{
+    pub fn new(shared: SharedDatabaseVector<T, C>) -> Self {
+        Self { inner: shared }
+    }
+
+    pub async fn len(&self) -> IndexType {
+        let inner = self.inner.clone();
+        tokio::task::spawn_blocking(move || inner.len())
+            .await
+            .expect("blocking storage task must not panic")
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+
+    pub async fn try_get(&self, index: IndexType) -> Result<T, DatabaseVectorError> {
+        let inner = self.inner.clone();
+        tokio::task::spawn_blocking(move || inner.try_get(index))
+            .await
+            .expect("blocking storage task must not panic")
+    }
+
+    pub async fn get(&self, index: IndexType) -> T {
+        let inner = self.inner.clone();
+        tokio::task::spawn_blocking(move || inner.get(index))
+            .await
+            .expect("blocking storage task must not panic")
+    }
+
+    pub async fn try_set(&self, index: IndexType, value: T) -> Result<(), DatabaseVectorError> {
+        let inner = self.inner.clone();
+        tokio::task::spawn_blocking(move || inner.try_set(index, value))
+            .await
+            .expect("blocking storage task must not panic")
+    }
+
+    pub async fn set(&self, index: IndexType, value: T) {
+        let inner = self.inner.clone();
+        tokio::task::spawn_blocking(move || inner.set(index, value))
+            .await
+            .expect("blocking storage task must not panic")
+    }
+
+    pub async fn push(&self, value: T) {
+        let inner = self.inner.clone();
+        tokio::task::spawn_blocking(move || inner.push(value))
+            .await
+            .expect("blocking storage task must not panic")
+    }
+
+    pub async fn pop(&self) -> Option<T> {
+        let inner = self.inner.clone();
+        tokio::task::spawn_blocking(move || inner.pop())
+            .await
+            .expect("blocking storage task must not panic")
+    }
+
+    pub async fn persist(&self) {
+        let inner = self.inner.clone();
+        tokio::task::spawn_blocking(move || inner.persist())
+            .await
+            .expect("blocking storage task must not panic")
+    }
+}
+
+impl<T, C> DatabaseVector<T, C>
+where
+    T: Clone + Send + 'static,
+    C: ValueCodec<T> + Send + 'static,
+{
+    /// Wrap this vector in an [`AsyncDatabaseVector`] handle that runs its blocking LevelDB calls
+    /// on tokio's blocking pool.
+    pub fn into_async(self) -> AsyncDatabaseVector<T, C> {
+        AsyncDatabaseVector::new(self.into_shared())
+    }
+}
+
+#[cfg(test)]
+mod async_database_vector_tests {
+    use super::*;
+    use rusty_leveldb::DB;
+
+    fn test_async_vector() -> AsyncDatabaseVector<u64> {
+        let opt = rusty_leveldb::in_memory();
+        let db = DB::open("mydatabase", opt).unwrap();
+        DatabaseVector::new(db).into_async()
+    }
+
+    #[tokio::test]
+    async fn push_and_get_round_trip_through_the_blocking_pool() {
+        let vector = test_async_vector();
+        vector.push(17).await;
+        vector.push(42).await;
+
+        assert_eq!(2, vector.len().await);
+        assert_eq!(17, vector.get(0).await);
+        assert_eq!(42, vector.get(1).await);
+    }
+
+    #[tokio::test]
+    async fn clones_observe_writes_made_through_each_other() {
+        let handle_a = test_async_vector();
+        let handle_b = handle_a.clone();
+
+        handle_a.push(1).await;
+        handle_b.set(0, 2).await;
+
+        assert_eq!(2, handle_a.get(0).await);
+    }
+
+    #[tokio::test]
+    async fn try_get_reports_out_of_bounds_indices() {
+        let vector = test_async_vector();
+        vector.push(1).await;
+
+        assert!(vector.try_get(5).await.is_err());
+    }
+
+    #[test]
+    fn concurrent_pushes_from_many_tasks_all_land() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let vector = test_async_vector();
+            let tasks: Vec<_> = (0..8)
+                .map(|_| {
+                    let vector = vector.clone();
+                    tokio::spawn(async move {
+                        for _ in 0..25 {
+                            vector.push(1).await;
+                        }
+                    })
+                })
+                .collect();
+            for task in tasks {
+                task.await.unwrap();
+            }
+
+            assert_eq!(200, vector.len().await);
+        });
+    }
+}
@@ -0,0 +1,86 @@
+//! Batched hashing of many leaf pairs at once, as needed when building Merkle trees or FRI
+//! commitments over very large leaf sets.
+//!
+//! Batches are hashed on the CPU using rayon. When compiled with the `gpu` feature, every batch is
+//! first offered to a GPU hook; no GPU kernel is implemented, so that hook always declines and the
+//! batch is hashed on the CPU exactly as without the feature.
+
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::shared_math::digest::Digest;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
+
+/// Below this many pairs, the overhead of parallel dispatch is not worth paying; hash
+/// sequentially instead.
+const BATCH_PARALLELLIZATION_THRESHOLD: usize = 16;
+
+/// Hash every `(left, right)` pair in `pairs` into a parent digest, in order.
+///
+/// This is the batched counterpart to repeatedly calling
+/// [`AlgebraicHasher::hash_pair`]; it is used for building Merkle trees and FRI commitments over
+/// large numbers of leaves, where the dominant cost is hashing rather than tree bookkeeping.
+pub fn hash_pairs_batch<H: AlgebraicHasher>(pairs: &[(Digest, Digest)]) -> Vec<Digest> {
+    #[cfg(feature = "gpu")]
+    if let Some(digests) = gpu::try_hash_pairs_batch(pairs) {
+        return digests;
+    }
+
+    if pairs.len() < BATCH_PARALLELLIZATION_THRESHOLD {
+        pairs
+            .iter()
+            .map(|(left, right)| H::hash_pair(left, right))
+            .collect()
+    } else {
+        pairs
+            .par_iter()
+            .map(|(left, right)| H::hash_pair(left, right))
+            .collect()
+    }
+}
+
+#[cfg(feature = "gpu")]
+mod gpu {
+    use super::*;
+
+    /// Attempt to hash `pairs` on a GPU device, returning `None` to signal that the caller
+    /// should fall back to the CPU backend.
+    ///
+    /// No GPU kernel is implemented in this crate yet: the CUDA/wgpu backend requires a build
+    /// environment and hardware this crate does not assume, so this function always defers to
+    /// the CPU path. It exists so that callers and downstream crates can compile against a
+    /// stable API now and swap in a real device dispatch later without changing call sites.
+    pub(super) fn try_hash_pairs_batch(_pairs: &[(Digest, Digest)]) -> Option<Vec<Digest>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod batch_hasher_tests {
+    use crate::shared_math::other::random_elements;
+    use crate::shared_math::tip5::Tip5;
+
+    use super::*;
+
+    #[test]
+    fn hash_pairs_batch_agrees_with_hash_pair() {
+        let leaves: Vec<Digest> = random_elements(64);
+        let pairs: Vec<(Digest, Digest)> = leaves
+            .iter()
+            .copied()
+            .zip(leaves.iter().copied().rev())
+            .collect();
+
+        let batched = hash_pairs_batch::<Tip5>(&pairs);
+        let sequential = pairs
+            .iter()
+            .map(|(left, right)| Tip5::hash_pair(left, right))
+            .collect::<Vec<_>>();
+
+        assert_eq!(sequential, batched);
+    }
+
+    #[test]
+    fn empty_batch_is_empty() {
+        assert!(hash_pairs_batch::<Tip5>(&[]).is_empty());
+    }
+}
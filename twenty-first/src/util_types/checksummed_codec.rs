@@ -0,0 +1,148 @@
+use std::marker::PhantomData;
+
+use crate::shared_math::b_field_element::BFieldElement;
+use crate::shared_math::digest::Digest;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
+
+use super::database_vector_codec::{BincodeCodec, ValueCodec};
+
+/// A stored value's bytes didn't hash to the [`Digest`] stored alongside them: bit rot, a
+/// truncated write, or a database written under a different [`ChecksummedCodec`] configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChecksumMismatch {
+    pub expected: Digest,
+    pub actual: Digest,
+}
+
+impl std::fmt::Display for ChecksumMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Checksum mismatch: expected {}, got {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for ChecksumMismatch {}
+
+/// Hash raw bytes with `H` by packing them into [`BFieldElement`]s 8 bytes at a time (the last
+/// chunk zero-padded), the same trick [`super::database_vector_codec::BFieldValueCodec`] uses in
+/// reverse.
+fn digest_of<H: AlgebraicHasher>(bytes: &[u8]) -> Digest {
+    let elements: Vec<BFieldElement> = bytes
+        .chunks(8)
+        .map(|chunk| {
+            let mut padded = [0u8; 8];
+            padded[..chunk.len()].copy_from_slice(chunk);
+            BFieldElement::new(u64::from_le_bytes(padded))
+        })
+        .collect();
+    H::hash_varlen(&elements)
+}
+
+/// A [`ValueCodec`] that wraps another codec `Inner` (default [`BincodeCodec`]) and prepends a
+/// [`Digest`] of the encoded bytes, computed with `H`. Meant for archival
+/// [`super::database_vector::DatabaseVector`]s, where undetected on-disk bit rot would otherwise
+/// be handed straight to `Inner::decode_value`.
+///
+/// [`ValueCodec::decode_value`] panics on a mismatch, matching every other codec's contract of
+/// treating malformed input as a programmer/environment error rather than a recoverable one. To
+/// get the mismatch back as a value instead, call [`Self::try_decode_value`] directly.
+pub struct ChecksummedCodec<H, Inner = BincodeCodec> {
+    _hasher: PhantomData<H>,
+    _inner: PhantomData<Inner>,
+}
+
+impl<H: AlgebraicHasher, Inner> ChecksummedCodec<H, Inner> {
+    /// Verify and strip the checksum, handing the remaining bytes to `Inner::decode_value`. Never
+    /// panics on a checksum mismatch; that path is only exercised through
+    /// [`ValueCodec::decode_value`].
+    pub fn try_decode_value<T>(bytes: &[u8]) -> Result<T, ChecksumMismatch>
+    where
+        Inner: ValueCodec<T>,
+    {
+        let (digest_bytes, payload) = bytes.split_at(Digest::BYTES);
+        let expected = Digest::new(
+            digest_bytes
+                .chunks_exact(8)
+                .map(|chunk| BFieldElement::new(u64::from_le_bytes(chunk.try_into().unwrap())))
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+        );
+        let actual = digest_of::<H>(payload);
+        if actual != expected {
+            return Err(ChecksumMismatch { expected, actual });
+        }
+        Ok(Inner::decode_value(payload))
+    }
+}
+
+impl<T, H: AlgebraicHasher, Inner: ValueCodec<T>> ValueCodec<T> for ChecksummedCodec<H, Inner> {
+    fn encode_value(value: &T) -> Vec<u8> {
+        let payload = Inner::encode_value(value);
+        let digest = digest_of::<H>(&payload);
+        digest
+            .values()
+            .iter()
+            .flat_map(|bfe| bfe.value().to_le_bytes())
+            .chain(payload)
+            .collect()
+    }
+
+    fn decode_value(bytes: &[u8]) -> T {
+        Self::try_decode_value(bytes).expect("Stored value must pass its checksum")
+    }
+}
+
+#[cfg(test)]
+mod checksummed_codec_tests {
+    use super::*;
+    use crate::shared_math::tip5::Tip5;
+
+    type Codec = ChecksummedCodec<Tip5, BincodeCodec>;
+
+    #[test]
+    fn round_trips_a_value_whose_bytes_are_untouched() {
+        let encoded = Codec::encode_value(&1337u64);
+        let decoded: u64 = Codec::decode_value(&encoded);
+        assert_eq!(1337u64, decoded);
+        assert_eq!(Ok(1337u64), Codec::try_decode_value::<u64>(&encoded));
+    }
+
+    #[test]
+    fn try_decode_value_reports_a_mismatch_instead_of_returning_garbage() {
+        let mut encoded = Codec::encode_value(&1337u64);
+        *encoded.last_mut().unwrap() ^= 0xff;
+
+        let result: Result<u64, ChecksumMismatch> = Codec::try_decode_value(&encoded);
+        assert!(result.is_err());
+    }
+
+    #[should_panic]
+    #[test]
+    fn decode_value_panics_on_a_mismatch() {
+        let mut encoded = Codec::encode_value(&1337u64);
+        *encoded.last_mut().unwrap() ^= 0xff;
+        let _: u64 = Codec::decode_value(&encoded);
+    }
+
+    #[test]
+    fn database_vector_persists_and_reads_back_values_via_the_checksummed_codec() {
+        use crate::util_types::database_vector::DatabaseVector;
+        use rusty_leveldb::DB;
+
+        let opt = rusty_leveldb::in_memory();
+        let db = DB::open("mydatabase", opt).unwrap();
+        let mut db_vector: DatabaseVector<u64, Codec> = DatabaseVector::new(db);
+
+        db_vector.push(42);
+        db_vector.push(1337);
+        assert_eq!(42, db_vector.get(0));
+        assert_eq!(1337, db_vector.get(1));
+
+        db_vector.persist();
+        assert_eq!(vec![42, 1337], db_vector.iter().collect::<Vec<_>>());
+    }
+}
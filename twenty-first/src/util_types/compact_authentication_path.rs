@@ -0,0 +1,118 @@
+//! A compact representation of a single-leaf authentication path.
+//!
+//! `twenty-first`'s existing path producers ([`MerkleTree::authentication_paths_from_authentication_structure`](crate::util_types::merkle_tree::MerkleTree::authentication_paths_from_authentication_structure),
+//! [`MmrMembershipProof::authentication_path`](crate::util_types::mmr::mmr_membership_proof::MmrMembershipProof))
+//! already store nothing but sibling digests: which side each sibling is on is derived from the
+//! leaf/node index during verification rather than carried alongside every digest, so there is no
+//! per-node `bool` left to strip out of those types. [`CompactAuthenticationPath`] instead packs
+//! the directions for a whole path into a single bitmask, giving a self-contained wire format that
+//! can be decoded without also shipping the leaf index or tree height next to it.
+use get_size::GetSize;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::shared_math::bfield_codec::BFieldCodec;
+use crate::shared_math::digest::Digest;
+
+/// A single-leaf authentication path with directions packed into a bitmask, bit `i` set if the
+/// leaf's ancestor at level `i` (0 = the leaf itself, counting up towards the root) is a
+/// *right* child of its parent, i.e. its sibling digest is the left one.
+///
+/// Limited to paths of at most 64 digests -- comfortably more than any tree height addressable by
+/// a `u64` leaf index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, GetSize, BFieldCodec)]
+pub struct CompactAuthenticationPath {
+    directions: u64,
+    digests: Vec<Digest>,
+}
+
+impl CompactAuthenticationPath {
+    /// Pack a plain authentication path for a leaf of a perfect binary tree -- ordered from the
+    /// leaf's sibling up to the root, as produced by
+    /// [`MerkleTree::authentication_paths_from_authentication_structure`](crate::util_types::merkle_tree::MerkleTree::authentication_paths_from_authentication_structure)
+    /// -- together with the authenticated leaf's own index.
+    pub fn new(leaf_index: u64, digests: Vec<Digest>) -> Self {
+        let num_leaves = 1u64 << digests.len();
+        Self::from_node_index(leaf_index + num_leaves, digests)
+    }
+
+    /// Pack a plain authentication path together with the node index its first digest is a
+    /// sibling of. Unlike [`Self::new`], this does not assume the path climbs a perfect binary
+    /// tree rooted at index 1, so it also applies to an
+    /// [`MmrMembershipProof`](crate::util_types::mmr::mmr_membership_proof::MmrMembershipProof)'s
+    /// `authentication_path`, whose starting node index is the leaf's position within its own
+    /// peak's subtree.
+    pub fn from_node_index(mut node_index: u64, digests: Vec<Digest>) -> Self {
+        assert!(
+            digests.len() <= u64::BITS as usize,
+            "Authentication path of length {} is too long to pack into a u64 bitmask.",
+            digests.len()
+        );
+
+        let mut directions = 0u64;
+        for level in 0..digests.len() {
+            if node_index % 2 == 1 {
+                directions |= 1 << level;
+            }
+            node_index /= 2;
+        }
+
+        Self {
+            directions,
+            digests,
+        }
+    }
+
+    /// True if the sibling digest at `level` (0 = closest to the leaf) is the *left* one, i.e. the
+    /// authenticated node is itself the right child at that level.
+    pub fn is_left_sibling(&self, level: usize) -> bool {
+        (self.directions >> level) & 1 == 1
+    }
+
+    /// The sibling digests, ordered from the leaf's sibling up to the root, exactly as produced
+    /// by the plain `Vec<Digest>` authentication path this was packed from.
+    pub fn digests(&self) -> &[Digest] {
+        &self.digests
+    }
+
+    pub fn len(&self) -> usize {
+        self.digests.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.digests.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod compact_authentication_path_tests {
+    use crate::shared_math::other::random_elements;
+
+    use super::*;
+
+    #[test]
+    fn packed_directions_match_the_leaf_index_bit_by_bit() {
+        let digests: Vec<Digest> = random_elements(5);
+        for leaf_index in 0..32u64 {
+            let compact = CompactAuthenticationPath::new(leaf_index, digests.clone());
+            for level in 0..digests.len() {
+                let expected_is_right = (leaf_index >> level) & 1 == 1;
+                assert_eq!(expected_is_right, compact.is_left_sibling(level));
+            }
+        }
+    }
+
+    #[test]
+    fn digests_round_trip_unchanged() {
+        let digests: Vec<Digest> = random_elements(7);
+        let compact = CompactAuthenticationPath::new(1234, digests.clone());
+        assert_eq!(digests, compact.digests());
+        assert_eq!(digests.len(), compact.len());
+    }
+
+    #[test]
+    fn empty_path_is_empty() {
+        let compact = CompactAuthenticationPath::new(0, vec![]);
+        assert!(compact.is_empty());
+    }
+}
@@ -4,13 +4,28 @@ use std::marker::PhantomData;
 
 type IndexType = u128;
 
-/// Permanent storage of a fixed-length array with elements of type `T`.
+/// Permanent storage of a fixed-length array with elements of type `T`. Unlike
+/// [`super::database_vector::DatabaseVector`], the length `N` is a compile-time constant rather
+/// than a value tracked in the database, so there's no reserved length key, no `push`/`pop`, and
+/// no bookkeeping write on every mutation — the right tradeoff for fixed-size tables such as a
+/// 2^20-entry lookup table, where `DatabaseVector`'s length tracking would be pure overhead.
 pub struct DatabaseArray<const N: IndexType, T: Serialize + DeserializeOwned + Default> {
     db: DB,
     _type: PhantomData<T>,
 }
 
 impl<const N: IndexType, T: Serialize + DeserializeOwned + Default> DatabaseArray<N, T> {
+    /// The array's fixed length, `N`.
+    pub const fn len(&self) -> IndexType {
+        N
+    }
+
+    /// A `DatabaseArray` of positive length is never empty; this only exists for parity with the
+    /// common `len`/`is_empty` pairing.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
     /// Return the element at position index. Returns `T::defeault()` if value is unset
     pub fn get(&mut self, index: IndexType) -> T {
         assert!(
@@ -111,6 +126,21 @@ mod database_array_tests {
         assert_eq!(0u64, db_array.get(79));
     }
 
+    #[test]
+    fn len_and_is_empty_reflect_the_const_generic_length() {
+        let opt = rusty_leveldb::in_memory();
+        let db = DB::open("mydatabase", opt).unwrap();
+        let db_array: DatabaseArray<101, u64> = DatabaseArray::new(db);
+        assert_eq!(101, db_array.len());
+        assert!(!db_array.is_empty());
+
+        let opt = rusty_leveldb::in_memory();
+        let empty_db = DB::open("myemptydatabase", opt).unwrap();
+        let empty_array: DatabaseArray<0, u64> = DatabaseArray::new(empty_db);
+        assert_eq!(0, empty_array.len());
+        assert!(empty_array.is_empty());
+    }
+
     #[should_panic = "Cannot get outside of length. Length: 101, index: 101"]
     #[test]
     fn panic_on_index_out_of_range_empty_test() {
@@ -0,0 +1,162 @@
+use rusty_leveldb::{LdbIterator, DB};
+use serde::{de::DeserializeOwned, Serialize};
+use std::marker::PhantomData;
+
+/// Persistent key-value map backed by a LevelDB instance. Keys and values are stored as their
+/// `bincode` serializations. This exists so that applications that need keyed persistent data
+/// don't have to fake it with a [`super::database_vector::DatabaseVector`] plus an in-memory
+/// index from key to vector position.
+pub struct DatabaseMap<K: Serialize + DeserializeOwned, V: Serialize + DeserializeOwned> {
+    db: DB,
+    _key_type: PhantomData<K>,
+    _value_type: PhantomData<V>,
+}
+
+impl<K: Serialize + DeserializeOwned, V: Serialize + DeserializeOwned> DatabaseMap<K, V> {
+    /// Wrap a database as a `DatabaseMap`. The database may already contain entries, e.g. when
+    /// restoring a map that was previously populated.
+    pub fn new(db: DB) -> Self {
+        Self {
+            db,
+            _key_type: PhantomData,
+            _value_type: PhantomData,
+        }
+    }
+
+    /// Look up the value stored for `key`, if any.
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let key_bytes = bincode::serialize(key).unwrap();
+        self.db
+            .get(&key_bytes)
+            .map(|value_bytes| bincode::deserialize(&value_bytes).unwrap())
+    }
+
+    /// Returns `true` if the map has an entry for `key`.
+    pub fn contains_key(&mut self, key: &K) -> bool {
+        let key_bytes = bincode::serialize(key).unwrap();
+        self.db.get(&key_bytes).is_some()
+    }
+
+    /// Insert `value` under `key`, returning the previous value stored there, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let key_bytes = bincode::serialize(&key).unwrap();
+        let previous = self
+            .db
+            .get(&key_bytes)
+            .map(|value_bytes| bincode::deserialize(&value_bytes).unwrap());
+
+        let value_bytes = bincode::serialize(&value).unwrap();
+        self.db
+            .put(&key_bytes, &value_bytes)
+            .expect("Insert must succeed");
+        previous
+    }
+
+    /// Remove the entry for `key`, returning its value, if it was present.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let key_bytes = bincode::serialize(key).unwrap();
+        let previous = self
+            .db
+            .get(&key_bytes)
+            .map(|value_bytes| bincode::deserialize(&value_bytes).unwrap());
+        if previous.is_some() {
+            self.db.delete(&key_bytes).expect("Delete must succeed");
+        }
+        previous
+    }
+
+    /// Iterate over every entry via a single LevelDB scan. Entries are visited in the raw
+    /// key-byte order LevelDB stores them in, which is *not* generally the same as any ordering
+    /// on `K` itself — callers that need a specific order must sort the result themselves.
+    pub fn iter(&mut self) -> impl Iterator<Item = (K, V)> + '_ {
+        let mut db_iterator = self
+            .db
+            .new_iter()
+            .expect("Opening a LevelDB iterator must succeed");
+        let mut entries = Vec::new();
+        while let Some((key_bytes, value_bytes)) = db_iterator.next() {
+            let key: K = bincode::deserialize(&key_bytes).unwrap();
+            let value: V = bincode::deserialize(&value_bytes).unwrap();
+            entries.push((key, value));
+        }
+        entries.into_iter()
+    }
+
+    pub fn flush(&mut self) {
+        self.db.flush().expect("Flush must succeed.")
+    }
+
+    /// Dispose of the map and return the database. You should probably only use this for testing.
+    pub fn extract_db(self) -> DB {
+        self.db
+    }
+}
+
+#[cfg(test)]
+mod database_map_tests {
+    use super::*;
+
+    fn test_constructor() -> DatabaseMap<String, u64> {
+        let opt = rusty_leveldb::in_memory();
+        let db = DB::open("mydatabase", opt).unwrap();
+        DatabaseMap::new(db)
+    }
+
+    #[test]
+    fn get_returns_none_for_a_missing_key() {
+        let mut map = test_constructor();
+        assert_eq!(None, map.get(&"missing".to_string()));
+        assert!(!map.contains_key(&"missing".to_string()));
+    }
+
+    #[test]
+    fn insert_and_get_round_trip() {
+        let mut map = test_constructor();
+        assert_eq!(None, map.insert("a".to_string(), 1));
+        assert_eq!(Some(1), map.get(&"a".to_string()));
+        assert!(map.contains_key(&"a".to_string()));
+
+        // Inserting again returns the previous value and overwrites it.
+        assert_eq!(Some(1), map.insert("a".to_string(), 2));
+        assert_eq!(Some(2), map.get(&"a".to_string()));
+    }
+
+    #[test]
+    fn remove_deletes_the_entry_and_returns_its_value() {
+        let mut map = test_constructor();
+        map.insert("a".to_string(), 1);
+
+        assert_eq!(Some(1), map.remove(&"a".to_string()));
+        assert_eq!(None, map.get(&"a".to_string()));
+        assert_eq!(None, map.remove(&"a".to_string()));
+    }
+
+    #[test]
+    fn iter_visits_every_entry() {
+        let mut map = test_constructor();
+        map.insert("a".to_string(), 1);
+        map.insert("b".to_string(), 2);
+        map.insert("c".to_string(), 3);
+
+        let mut entries = map.iter().collect::<Vec<_>>();
+        entries.sort();
+        assert_eq!(
+            vec![
+                ("a".to_string(), 1),
+                ("b".to_string(), 2),
+                ("c".to_string(), 3),
+            ],
+            entries
+        );
+    }
+
+    #[test]
+    fn restoring_from_an_existing_database_preserves_its_entries() {
+        let mut map = test_constructor();
+        map.insert("a".to_string(), 1);
+        let db = map.extract_db();
+
+        let mut restored: DatabaseMap<String, u64> = DatabaseMap::new(db);
+        assert_eq!(Some(1), restored.get(&"a".to_string()));
+    }
+}
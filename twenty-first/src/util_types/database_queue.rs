@@ -0,0 +1,242 @@
+use std::marker::PhantomData;
+
+use super::database_vector_codec::{BincodeCodec, ValueCodec};
+use super::database_vector_index::VectorIndex;
+use super::storage_backend::{BatchOperation, StorageBackend};
+
+/// Reserved keys for the queue's head and tail indices. See
+/// [`super::database_vector::DatabaseVector`]'s `LENGTH_KEY` for why 1-byte keys are safe here:
+/// every real element key is `size_of::<I>()` bytes (4, 8, or 16), never 1, so neither can
+/// collide with one.
+const HEAD_KEY: [u8; 1] = [0];
+const TAIL_KEY: [u8; 1] = [1];
+
+/// Persistent FIFO queue over any [`StorageBackend`]. Elements are never shifted: [`Self::enqueue`]
+/// writes at an ever-increasing `tail` index and [`Self::dequeue`] deletes and advances an
+/// ever-increasing `head` index, so both are `O(1)` instead of the `O(len())` a
+/// [`super::database_vector::DatabaseVector`] emulating a queue via `remove(0)` would pay.
+pub struct DatabaseQueue<
+    T,
+    B: StorageBackend,
+    C: ValueCodec<T> = BincodeCodec,
+    I: VectorIndex = u64,
+> {
+    backend: B,
+    _type: PhantomData<T>,
+    _codec: PhantomData<C>,
+    _index: PhantomData<I>,
+}
+
+impl<T, B: StorageBackend, C: ValueCodec<T>, I: VectorIndex> DatabaseQueue<T, B, C, I> {
+    fn head(&mut self) -> I {
+        self.backend
+            .get(&HEAD_KEY)
+            .map(|bytes| I::from_be_bytes_slice(&bytes))
+            .expect("Queue head must be set")
+    }
+
+    fn tail(&mut self) -> I {
+        self.backend
+            .get(&TAIL_KEY)
+            .map(|bytes| I::from_be_bytes_slice(&bytes))
+            .expect("Queue tail must be set")
+    }
+
+    fn set_head(&mut self, head: I) {
+        self.backend.put(&HEAD_KEY, &head.to_be_bytes_vec());
+    }
+
+    fn set_tail(&mut self, tail: I) {
+        self.backend.put(&TAIL_KEY, &tail.to_be_bytes_vec());
+    }
+
+    /// Wrap an empty `backend` as a fresh, empty queue. Panics if `backend` already holds a head
+    /// key.
+    pub fn new(backend: B) -> Self {
+        let mut queue = Self {
+            backend,
+            _type: PhantomData,
+            _codec: PhantomData,
+            _index: PhantomData,
+        };
+        assert!(
+            queue.backend.get(&HEAD_KEY).is_none(),
+            "Backend must be empty when instantiating a database queue with `new`"
+        );
+        queue.set_head(I::ZERO);
+        queue.set_tail(I::ZERO);
+        queue
+    }
+
+    /// Wrap a `backend` that already holds a queue written by an earlier [`Self::new`].
+    pub fn restore(backend: B) -> Self {
+        let mut queue = Self {
+            backend,
+            _type: PhantomData,
+            _codec: PhantomData,
+            _index: PhantomData,
+        };
+        let _dummy_res = queue.len();
+        queue
+    }
+
+    pub fn len(&mut self) -> I {
+        self.tail() - self.head()
+    }
+
+    pub fn is_empty(&mut self) -> bool {
+        self.len() == I::ZERO
+    }
+
+    pub fn enqueue(&mut self, value: T) {
+        let tail = self.tail();
+        let value_bytes = C::encode_value(&value);
+        self.backend.put(&tail.to_be_bytes_vec(), &value_bytes);
+        self.set_tail(tail + I::ONE);
+    }
+
+    pub fn dequeue(&mut self) -> Option<T> {
+        let head = self.head();
+        if head == self.tail() {
+            return None;
+        }
+
+        let bytes = self
+            .backend
+            .get(&head.to_be_bytes_vec())
+            .expect("Queue head element must exist");
+        self.backend.delete(&head.to_be_bytes_vec());
+        self.set_head(head + I::ONE);
+        Some(C::decode_value(&bytes))
+    }
+
+    /// Look at the front element without removing it.
+    pub fn peek(&mut self) -> Option<T> {
+        let head = self.head();
+        if head == self.tail() {
+            return None;
+        }
+
+        self.backend
+            .get(&head.to_be_bytes_vec())
+            .map(|bytes| C::decode_value(&bytes))
+    }
+
+    /// Enqueue every element of `values` in a single write batch, the batched counterpart to
+    /// [`Self::enqueue`].
+    pub fn enqueue_many(&mut self, values: &[T]) {
+        if values.is_empty() {
+            return;
+        }
+
+        let mut tail = self.tail();
+        let mut operations = Vec::with_capacity(values.len() + 1);
+        for value in values {
+            let value_bytes = C::encode_value(value);
+            operations.push(BatchOperation::Put(tail.to_be_bytes_vec(), value_bytes));
+            tail = tail + I::ONE;
+        }
+        operations.push(BatchOperation::Put(
+            TAIL_KEY.to_vec(),
+            tail.to_be_bytes_vec(),
+        ));
+        self.backend.write_batch(operations);
+    }
+
+    /// Dequeue up to `n` elements in a single write batch, the batched counterpart to
+    /// [`Self::dequeue`]. Returns fewer than `n` elements if the queue holds less, oldest first.
+    pub fn dequeue_many(&mut self, n: usize) -> Vec<T> {
+        let head = self.head();
+        let tail = self.tail();
+        let n = n.min((tail - head).to_usize());
+        if n == 0 {
+            return Vec::new();
+        }
+
+        let new_head = head + I::from_usize(n);
+        let mut dequeued = Vec::with_capacity(n);
+        let mut operations = Vec::with_capacity(n + 1);
+        let mut index = head;
+        while index < new_head {
+            let bytes = self
+                .backend
+                .get(&index.to_be_bytes_vec())
+                .expect("Queue element must exist");
+            dequeued.push(C::decode_value(&bytes));
+            operations.push(BatchOperation::Delete(index.to_be_bytes_vec()));
+            index = index + I::ONE;
+        }
+        operations.push(BatchOperation::Put(
+            HEAD_KEY.to_vec(),
+            new_head.to_be_bytes_vec(),
+        ));
+        self.backend.write_batch(operations);
+        dequeued
+    }
+}
+
+#[cfg(test)]
+mod database_queue_tests {
+    use super::*;
+    use crate::util_types::storage_backend::InMemoryBackend;
+
+    fn test_queue() -> DatabaseQueue<u64, InMemoryBackend> {
+        DatabaseQueue::new(InMemoryBackend::new())
+    }
+
+    #[test]
+    fn enqueue_dequeue_and_peek_behave_like_a_fifo_queue() {
+        let mut queue = test_queue();
+        assert!(queue.is_empty());
+        assert_eq!(None, queue.dequeue());
+        assert_eq!(None, queue.peek());
+
+        queue.enqueue(1);
+        queue.enqueue(2);
+        queue.enqueue(3);
+        assert_eq!(3, queue.len());
+        assert_eq!(Some(1), queue.peek());
+
+        assert_eq!(Some(1), queue.dequeue());
+        assert_eq!(Some(2), queue.dequeue());
+        assert_eq!(1, queue.len());
+        assert_eq!(Some(3), queue.dequeue());
+        assert_eq!(None, queue.dequeue());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn enqueue_many_and_dequeue_many_batch_correctly() {
+        let mut queue = test_queue();
+        queue.enqueue_many(&[1, 2, 3]);
+        assert_eq!(3, queue.len());
+
+        assert_eq!(vec![1, 2], queue.dequeue_many(2));
+        assert_eq!(1, queue.len());
+
+        assert_eq!(vec![3], queue.dequeue_many(10));
+        assert!(queue.is_empty());
+        assert_eq!(Vec::<u64>::new(), queue.dequeue_many(1));
+    }
+
+    #[test]
+    fn dequeuing_and_reenqueuing_never_reuses_an_index() {
+        let mut queue = test_queue();
+        queue.enqueue(1);
+        queue.dequeue();
+        queue.enqueue(2);
+
+        assert_eq!(Some(2), queue.dequeue());
+    }
+
+    #[test]
+    fn restore_recovers_an_existing_queue() {
+        let mut queue = test_queue();
+        queue.enqueue(42);
+        let backend = queue.backend;
+
+        let mut restored: DatabaseQueue<u64, InMemoryBackend> = DatabaseQueue::restore(backend);
+        assert_eq!(1, restored.len());
+        assert_eq!(Some(42), restored.dequeue());
+    }
+}
@@ -0,0 +1,220 @@
+use std::marker::PhantomData;
+
+use super::database_vector_codec::{BincodeCodec, ValueCodec};
+use super::database_vector_index::VectorIndex;
+use super::storage_backend::{BatchOperation, StorageBackend};
+
+/// Reserved key for the stack's length. See [`super::database_vector::DatabaseVector`]'s
+/// `LENGTH_KEY` for why a 1-byte key is safe: every real element key is `size_of::<I>()` bytes
+/// (4, 8, or 16), never 1, so it can't collide.
+const LENGTH_KEY: [u8; 1] = [0];
+
+/// Persistent LIFO stack over any [`StorageBackend`]. Downstream applications that only ever
+/// push/pop at the end and don't need [`super::database_vector::DatabaseVector`]'s random access
+/// or iteration were emulating this awkwardly on top of it; this is the direct primitive instead.
+pub struct DatabaseStack<
+    T,
+    B: StorageBackend,
+    C: ValueCodec<T> = BincodeCodec,
+    I: VectorIndex = u64,
+> {
+    backend: B,
+    _type: PhantomData<T>,
+    _codec: PhantomData<C>,
+    _index: PhantomData<I>,
+}
+
+impl<T, B: StorageBackend, C: ValueCodec<T>, I: VectorIndex> DatabaseStack<T, B, C, I> {
+    fn persisted_length(&mut self) -> I {
+        self.backend
+            .get(&LENGTH_KEY)
+            .map(|bytes| I::from_be_bytes_slice(&bytes))
+            .expect("Stack length must be set")
+    }
+
+    fn set_length(&mut self, length: I) {
+        self.backend.put(&LENGTH_KEY, &length.to_be_bytes_vec());
+    }
+
+    /// Wrap an empty `backend` as a fresh, empty stack. Panics if `backend` already holds an
+    /// element at index zero, the same sanity check
+    /// [`super::database_vector::DatabaseVector::new`] does.
+    pub fn new(backend: B) -> Self {
+        let mut stack = Self {
+            backend,
+            _type: PhantomData,
+            _codec: PhantomData,
+            _index: PhantomData,
+        };
+        assert!(
+            stack.backend.get(&I::ZERO.to_be_bytes_vec()).is_none(),
+            "Backend must be empty when instantiating a database stack with `new`"
+        );
+        stack.set_length(I::ZERO);
+        stack
+    }
+
+    /// Wrap a `backend` that already holds a stack written by an earlier [`Self::new`].
+    pub fn restore(backend: B) -> Self {
+        let mut stack = Self {
+            backend,
+            _type: PhantomData,
+            _codec: PhantomData,
+            _index: PhantomData,
+        };
+        let _dummy_res = stack.len();
+        stack
+    }
+
+    pub fn len(&mut self) -> I {
+        self.persisted_length()
+    }
+
+    pub fn is_empty(&mut self) -> bool {
+        self.len() == I::ZERO
+    }
+
+    pub fn push(&mut self, value: T) {
+        let length = self.persisted_length();
+        let value_bytes = C::encode_value(&value);
+        self.backend.put(&length.to_be_bytes_vec(), &value_bytes);
+        self.set_length(length + I::ONE);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let length = self.persisted_length();
+        if length == I::ZERO {
+            return None;
+        }
+
+        let top = length - I::ONE;
+        let bytes = self
+            .backend
+            .get(&top.to_be_bytes_vec())
+            .expect("Stack top must exist");
+        self.backend.delete(&top.to_be_bytes_vec());
+        self.set_length(top);
+        Some(C::decode_value(&bytes))
+    }
+
+    /// Look at the top element without removing it.
+    pub fn peek(&mut self) -> Option<T> {
+        let length = self.persisted_length();
+        if length == I::ZERO {
+            return None;
+        }
+
+        let top = length - I::ONE;
+        self.backend
+            .get(&top.to_be_bytes_vec())
+            .map(|bytes| C::decode_value(&bytes))
+    }
+
+    /// Push every element of `values` in a single write batch, the batched counterpart to
+    /// [`Self::push`].
+    pub fn push_many(&mut self, values: &[T]) {
+        if values.is_empty() {
+            return;
+        }
+
+        let mut length = self.persisted_length();
+        let mut operations = Vec::with_capacity(values.len() + 1);
+        for value in values {
+            let value_bytes = C::encode_value(value);
+            operations.push(BatchOperation::Put(length.to_be_bytes_vec(), value_bytes));
+            length = length + I::ONE;
+        }
+        operations.push(BatchOperation::Put(
+            LENGTH_KEY.to_vec(),
+            length.to_be_bytes_vec(),
+        ));
+        self.backend.write_batch(operations);
+    }
+
+    /// Pop up to `n` elements in a single write batch, the batched counterpart to [`Self::pop`].
+    /// Returns fewer than `n` elements if the stack holds less, in the order [`Self::pop`] would
+    /// return them: most recently pushed first.
+    pub fn pop_many(&mut self, n: usize) -> Vec<T> {
+        let old_length = self.persisted_length();
+        let n = n.min(old_length.to_usize());
+        if n == 0 {
+            return Vec::new();
+        }
+
+        let new_length = old_length.saturating_sub(I::from_usize(n));
+        let mut popped = Vec::with_capacity(n);
+        let mut operations = Vec::with_capacity(n + 1);
+        let mut index = old_length;
+        while index > new_length {
+            index = index - I::ONE;
+            let bytes = self
+                .backend
+                .get(&index.to_be_bytes_vec())
+                .expect("Stack element must exist");
+            popped.push(C::decode_value(&bytes));
+            operations.push(BatchOperation::Delete(index.to_be_bytes_vec()));
+        }
+        operations.push(BatchOperation::Put(
+            LENGTH_KEY.to_vec(),
+            new_length.to_be_bytes_vec(),
+        ));
+        self.backend.write_batch(operations);
+        popped
+    }
+}
+
+#[cfg(test)]
+mod database_stack_tests {
+    use super::*;
+    use crate::util_types::storage_backend::InMemoryBackend;
+
+    fn test_stack() -> DatabaseStack<u64, InMemoryBackend> {
+        DatabaseStack::new(InMemoryBackend::new())
+    }
+
+    #[test]
+    fn push_pop_and_peek_behave_like_a_stack() {
+        let mut stack = test_stack();
+        assert!(stack.is_empty());
+        assert_eq!(None, stack.pop());
+        assert_eq!(None, stack.peek());
+
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
+        assert_eq!(3, stack.len());
+        assert_eq!(Some(3), stack.peek());
+
+        assert_eq!(Some(3), stack.pop());
+        assert_eq!(Some(2), stack.pop());
+        assert_eq!(1, stack.len());
+        assert_eq!(Some(1), stack.pop());
+        assert_eq!(None, stack.pop());
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn push_many_and_pop_many_batch_correctly() {
+        let mut stack = test_stack();
+        stack.push_many(&[1, 2, 3]);
+        assert_eq!(3, stack.len());
+
+        assert_eq!(vec![3, 2], stack.pop_many(2));
+        assert_eq!(1, stack.len());
+
+        assert_eq!(vec![1], stack.pop_many(10));
+        assert!(stack.is_empty());
+        assert_eq!(Vec::<u64>::new(), stack.pop_many(1));
+    }
+
+    #[test]
+    fn restore_recovers_an_existing_stack() {
+        let mut stack = test_stack();
+        stack.push(42);
+        let backend = stack.backend;
+
+        let mut restored: DatabaseStack<u64, InMemoryBackend> = DatabaseStack::restore(backend);
+        assert_eq!(1, restored.len());
+        assert_eq!(Some(42), restored.pop());
+    }
+}
@@ -1,61 +1,289 @@
-use rusty_leveldb::{WriteBatch, DB};
-use serde::{de::DeserializeOwned, Serialize};
+use anyhow::{bail, Result};
+use rusty_leveldb::{LdbIterator, WriteBatch, DB};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
 use std::marker::PhantomData;
+use std::path::Path;
+
+use super::database_vector_codec::{BincodeCodec, ValueCodec};
+use super::database_vector_index::VectorIndex;
+use super::lru_cache::LruCache;
+
+/// Version tag prepended to every [`DatabaseVector::export_to_file`] dump, so
+/// [`DatabaseVector::import_from_file`] can reject a dump written by an incompatible format.
+const EXPORT_FORMAT_VERSION: u32 = 1;
 
 /// This is the key for the storage of the length of the vector
 /// Due to a bug in rusty-levelDB we use 1 byte, not 0 bytes to store the length
 /// of the vector. Cf. https://github.com/dermesser/leveldb-rs/issues/16
-/// This is OK to do as long as collide with a key. Since the keys for indices
-/// are all 16 bytes long when using 128s, then its OK to use a 1-byte key here.
+/// This is OK to do regardless of the vector's index type `I`, since every real index key is
+/// encoded as exactly `size_of::<I>()` bytes (4, 8, or 16), never 1, so a 1-byte key can never
+/// collide with one.
 const LENGTH_KEY: [u8; 1] = [0];
-type IndexType = u64;
-const INDEX_ZERO: IndexType = 0;
 
-pub struct DatabaseVector<T: Serialize + DeserializeOwned> {
+/// Key for the on-disk schema-version tag, stored right alongside [`LENGTH_KEY`]. A database
+/// vector written before this tag existed has nothing at this key; [`DatabaseVector::restore`]
+/// treats that as schema version 0.
+const SCHEMA_VERSION_KEY: [u8; 1] = [1];
+
+/// The schema version [`DatabaseVector::new`] writes into fresh databases. Bump this, and add a
+/// [`DatabaseVector::migrate`] call for the previous value, whenever a change to the index width
+/// or value encoding would otherwise silently corrupt databases written by an older crate version
+/// instead of failing loudly.
+const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+/// Failure modes for the `try_*` methods on [`DatabaseVector`], reported instead of the panics
+/// that their non-fallible counterparts (`get`, `set`, `push`, `len`) raise for the same
+/// conditions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DatabaseVectorError<I: VectorIndex = u64> {
+    /// `index` was accessed on a vector of only `length` elements.
+    IndexOutOfBounds { index: I, length: I },
+    /// The reserved length key is missing from the database, so the vector's length can't be
+    /// determined. This indicates a database that either isn't a `DatabaseVector` or is corrupt.
+    LengthMissing,
+    /// An underlying LevelDB read or write failed.
+    Database(rusty_leveldb::Status),
+}
+
+impl<I: VectorIndex> std::fmt::Display for DatabaseVectorError<I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl<I: VectorIndex> std::error::Error for DatabaseVectorError<I> {}
+
+impl<I: VectorIndex> From<rusty_leveldb::Status> for DatabaseVectorError<I> {
+    fn from(status: rusty_leveldb::Status) -> Self {
+        DatabaseVectorError::Database(status)
+    }
+}
+
+/// A point-in-time snapshot of a [`DatabaseVector`]'s size and buffering. See [`DatabaseVector::stats`].
+///
+/// Deliberately not generic over the vector's index type `I`: this is a monitoring-facing type,
+/// and `u64` comfortably covers every element count a `DatabaseVector` can hold in practice
+/// regardless of whether it's internally indexed by `u32`, `u64`, or `u128`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DatabaseVectorStats {
+    pub element_count: u64,
+    pub total_value_bytes: u64,
+    pub pending_writes: usize,
+}
+
+/// Persistent, growable vector backed by a LevelDB instance.
+///
+/// Elements are stored via the codec `C`, which defaults to [`BincodeCodec`]. Passing
+/// [`super::database_vector_codec::BFieldValueCodec`] instead makes the on-disk bytes match `T`'s
+/// canonical [`crate::shared_math::bfield_codec::BFieldCodec`] encoding, for types that have one.
+///
+/// Indices and the length are stored under the type `I` (default `u64`), see
+/// [`super::database_vector_index::VectorIndex`]. `I` is encoded as fixed-width big-endian bytes,
+/// so raw LevelDB key order matches numeric index order; pick `u32` to halve key size for vectors
+/// that will never need more than about four billion elements, or `u128` for vectors that might
+/// outgrow `u64::MAX`.
+pub struct DatabaseVector<T, C: ValueCodec<T> = BincodeCodec, I: VectorIndex = u64> {
     db: DB,
     _type: PhantomData<T>,
+    _codec: PhantomData<C>,
+    _index: PhantomData<I>,
+    /// Write-back overlay for [`Self::set`]-updated indices below the persisted length, flushed
+    /// to the database by [`Self::persist`].
+    cache: HashMap<I, T>,
+    /// Elements [`Self::push`]ed since the last [`Self::persist`] call, not yet given real
+    /// database keys.
+    pending_pushes: Vec<T>,
+    /// Bounded read cache for persisted indices, off by default. See [`Self::enable_read_cache`].
+    read_cache: Option<LruCache<I, T>>,
 }
 
-impl<T: Serialize + DeserializeOwned> DatabaseVector<T> {
-    fn set_length(&mut self, length: IndexType) {
-        let length_as_bytes = bincode::serialize(&length).unwrap();
-        self.db
-            .put(&LENGTH_KEY, &length_as_bytes)
+impl<T: Clone, C: ValueCodec<T>, I: VectorIndex> DatabaseVector<T, C, I> {
+    /// The length as of the last [`Self::persist`] call, read straight from the database with no
+    /// regard for [`Self::pending_pushes`].
+    fn persisted_length(&mut self) -> Result<I, DatabaseVectorError<I>> {
+        let length_as_bytes = self
+            .db
+            .get(&LENGTH_KEY)
+            .ok_or(DatabaseVectorError::LengthMissing)?;
+        Ok(I::from_be_bytes_slice(&length_as_bytes))
+    }
+
+    fn try_set_length(&mut self, length: I) -> Result<(), DatabaseVectorError<I>> {
+        self.db.put(&LENGTH_KEY, &length.to_be_bytes_vec())?;
+        Ok(())
+    }
+
+    fn set_length(&mut self, length: I) {
+        self.try_set_length(length)
             .expect("Length write must succeed");
     }
 
-    fn delete(&mut self, index: IndexType) {
-        let index_as_bytes = bincode::serialize(&index).unwrap();
+    fn persisted_schema_version(&mut self) -> u32 {
+        self.db
+            .get(&SCHEMA_VERSION_KEY)
+            .map(|bytes| bincode::deserialize(&bytes).unwrap())
+            .unwrap_or(0)
+    }
+
+    fn set_schema_version(&mut self, version: u32) {
+        let version_bytes = bincode::serialize(&version).unwrap();
+        self.db
+            .put(&SCHEMA_VERSION_KEY, &version_bytes)
+            .expect("Schema version write must succeed");
+    }
+
+    /// The schema version this vector's database was last written at. Freshly-[`Self::new`]ed
+    /// vectors report [`CURRENT_SCHEMA_VERSION`]; a vector [`Self::restore`]d from a database
+    /// written before this tag existed reports `0`.
+    pub fn schema_version(&mut self) -> u32 {
+        self.persisted_schema_version()
+    }
+
+    /// Run `migration` against this vector and advance its schema-version tag from
+    /// `from_version` to `from_version + 1`, but only if the vector is currently at
+    /// `from_version`. A no-op otherwise, which is what makes this safe to call unconditionally
+    /// on every startup: a chain of `migrate(0, ...)`, `migrate(1, ...)`, `migrate(2, ...)` walks
+    /// a database forward from whatever version it was actually written at, running only the
+    /// steps it still needs, and running each one exactly once.
+    pub fn migrate(&mut self, from_version: u32, migration: impl FnOnce(&mut Self)) {
+        if self.persisted_schema_version() != from_version {
+            return;
+        }
+        migration(self);
+        self.persist();
+        self.set_schema_version(from_version + 1);
+    }
+
+    fn delete(&mut self, index: I) {
         self.db
-            .delete(&index_as_bytes)
+            .delete(&index.to_be_bytes_vec())
             .expect("Deleting element must succeed");
+        if let Some(read_cache) = &mut self.read_cache {
+            read_cache.invalidate(&index);
+        }
+    }
+
+    /// Turn on a bounded in-memory LRU cache of up to `capacity` persisted `(index, value)`
+    /// pairs, checked by [`Self::get`]/[`Self::try_get`] before falling through to the database.
+    /// Useful for access patterns that repeatedly hit the same handful of indices, e.g. an MMR's
+    /// peak-path nodes.
+    ///
+    /// [`Self::set`] and [`Self::pop`] invalidate the affected entry; every other mutating method
+    /// (`insert`, `remove`, `truncate`, ...) can shift or replace indices wholesale, so they clear
+    /// the cache entirely rather than tracking exactly what moved.
+    pub fn enable_read_cache(&mut self, capacity: usize) {
+        self.read_cache = Some(LruCache::new(capacity));
+    }
+
+    /// Turn the read cache back off, discarding whatever it currently holds.
+    pub fn disable_read_cache(&mut self) {
+        self.read_cache = None;
+    }
+
+    fn invalidate_read_cache(&mut self) {
+        if let Some(read_cache) = &mut self.read_cache {
+            read_cache.clear();
+        }
     }
 
     /// Return true if the database vector looks empty. Used for sanity check when creating
     /// a new database vector.
     fn attempt_verify_empty(&mut self) -> bool {
-        let index_bytes: Vec<u8> = bincode::serialize(&INDEX_ZERO).unwrap();
-        self.db.get(&index_bytes).is_none()
+        self.db.get(&I::ZERO.to_be_bytes_vec()).is_none()
     }
 
     pub fn is_empty(&mut self) -> bool {
-        self.len() == 0
+        self.len() == I::ZERO
     }
 
     pub fn flush(&mut self) {
         self.db.flush().expect("Flush must succeed.")
     }
 
-    pub fn len(&mut self) -> IndexType {
-        let length_as_bytes = self.db.get(&LENGTH_KEY).expect("Length must exist");
-        bincode::deserialize(&length_as_bytes).unwrap()
+    /// Ask the underlying LevelDB instance to compact away deleted and overwritten entries across
+    /// the whole vector. Exposes `rusty_leveldb::DB::compact_range` so operators of long-running
+    /// nodes can trigger maintenance compactions without reaching into the database directly.
+    pub fn compact_range(&mut self) {
+        self.persist();
+        self.db
+            .compact_range(&[], &[0xffu8; 17])
+            .expect("Compaction must succeed");
+    }
+
+    /// Compute a point-in-time snapshot of this vector's size, for monitoring long-running nodes.
+    /// `rusty_leveldb` has no O(1) approximate-size query, so `total_value_bytes` costs a full
+    /// scan; the optional read cache does not count hits, so there is no hit rate to report, only
+    /// the write-back overlay's occupancy (see [`Self::is_dirty`]).
+    pub fn stats(&mut self) -> DatabaseVectorStats {
+        let pending_writes = self.cache.len() + self.pending_pushes.len();
+        let mut element_count = 0u64;
+        let mut total_value_bytes = 0u64;
+        for value in self.iter() {
+            element_count += 1;
+            total_value_bytes += C::encode_value(&value).len() as u64;
+        }
+        DatabaseVectorStats {
+            element_count,
+            total_value_bytes,
+            pending_writes,
+        }
+    }
+
+    pub fn try_len(&mut self) -> Result<I, DatabaseVectorError<I>> {
+        let persisted_length = self.persisted_length()?;
+        Ok(persisted_length + I::from_usize(self.pending_pushes.len()))
+    }
+
+    pub fn len(&mut self) -> I {
+        self.try_len().expect("Length must exist")
+    }
+
+    /// True if [`Self::set`] or [`Self::push`] has buffered a write that hasn't been flushed to
+    /// the database with [`Self::persist`] yet.
+    pub fn is_dirty(&self) -> bool {
+        !self.cache.is_empty() || !self.pending_pushes.is_empty()
+    }
+
+    /// Flush every [`Self::set`]- and [`Self::push`]-buffered write to the database as a single
+    /// write batch. Callers doing many small updates per commit should batch them behind `set`
+    /// and `push` and call `persist` once, rather than hitting LevelDB for every update.
+    pub fn persist(&mut self) {
+        let persisted_length = self.persisted_length().expect("Length must exist");
+        let mut batch_write = WriteBatch::new();
+
+        for (index, value) in self.cache.drain() {
+            let value_bytes: Vec<u8> = C::encode_value(&value);
+            batch_write.put(&index.to_be_bytes_vec(), &value_bytes);
+        }
+
+        let mut new_length = persisted_length;
+        for value in self.pending_pushes.drain(..) {
+            let value_bytes: Vec<u8> = C::encode_value(&value);
+            batch_write.put(&new_length.to_be_bytes_vec(), &value_bytes);
+            new_length = new_length + I::ONE;
+        }
+
+        if new_length != persisted_length {
+            batch_write.put(&LENGTH_KEY, &new_length.to_be_bytes_vec());
+        }
+
+        self.db
+            .write(batch_write, true)
+            .expect("Failed to batch-write to database in persist");
     }
 
     /// given a database containing a database vector, restore it into a database vector struct
     pub fn restore(db: DB) -> Self {
         let mut ret = Self {
             _type: PhantomData,
+            _codec: PhantomData,
+            _index: PhantomData,
             db,
+            cache: HashMap::new(),
+            pending_pushes: Vec::new(),
+            read_cache: None,
         };
 
         // sanity check to verify that the length is set
@@ -64,22 +292,25 @@ impl<T: Serialize + DeserializeOwned> DatabaseVector<T> {
     }
 
     pub fn overwrite_with_vec(&mut self, new_vector: Vec<T>) {
+        self.persist();
+        self.invalidate_read_cache();
         let old_length = self.len();
-        let new_length = new_vector.len() as IndexType;
+        let new_length = I::from_usize(new_vector.len());
         self.set_length(new_length);
 
         let mut batch_write = WriteBatch::new();
         for (index, val) in new_vector.into_iter().enumerate() {
             // Notice that `index` has to be cast to the type of the index for this data structure.
             // Otherwise this function will create a corrupted database.
-            let index_bytes: Vec<u8> = bincode::serialize(&(index as IndexType)).unwrap();
-            let value_bytes: Vec<u8> = bincode::serialize(&val).unwrap();
+            let index_bytes = I::from_usize(index).to_be_bytes_vec();
+            let value_bytes: Vec<u8> = C::encode_value(&val);
             batch_write.put(&index_bytes, &value_bytes);
         }
 
-        for index in new_length..old_length {
-            let index_bytes: Vec<u8> = bincode::serialize(&index).unwrap();
-            batch_write.delete(&index_bytes);
+        let mut index = new_length;
+        while index < old_length {
+            batch_write.delete(&index.to_be_bytes_vec());
+            index = index + I::ONE;
         }
 
         self.db
@@ -87,48 +318,169 @@ impl<T: Serialize + DeserializeOwned> DatabaseVector<T> {
             .expect("Failed to batch-write to database in overwrite_with_vec");
     }
 
+    /// Shorten the vector to `new_len` elements, deleting the trailing ones in a single write
+    /// batch instead of `pop`-ing them one at a time. Does nothing if `new_len >= len()`.
+    pub fn truncate(&mut self, new_len: I) {
+        self.persist();
+        self.invalidate_read_cache();
+        let old_length = self.len();
+        if new_len >= old_length {
+            return;
+        }
+
+        let mut batch_write = WriteBatch::new();
+        let mut index = new_len;
+        while index < old_length {
+            batch_write.delete(&index.to_be_bytes_vec());
+            index = index + I::ONE;
+        }
+
+        self.db
+            .write(batch_write, true)
+            .expect("Failed to batch-write to database in truncate");
+        self.set_length(new_len);
+    }
+
+    /// Remove every element, leaving an empty vector.
+    pub fn clear(&mut self) {
+        self.truncate(I::ZERO);
+    }
+
+    /// Grow or shrink the vector to exactly `new_len` elements. Shrinking deletes the trailing
+    /// elements, as in [`Self::truncate`]; growing appends `fill` for every new index.
+    pub fn resize(&mut self, new_len: I, fill: T) {
+        self.persist();
+        self.invalidate_read_cache();
+        let old_length = self.len();
+        if new_len <= old_length {
+            self.truncate(new_len);
+            return;
+        }
+
+        let mut batch_write = WriteBatch::new();
+        let mut index = old_length;
+        while index < new_len {
+            let value_bytes: Vec<u8> = C::encode_value(&fill);
+            batch_write.put(&index.to_be_bytes_vec(), &value_bytes);
+            index = index + I::ONE;
+        }
+
+        self.db
+            .write(batch_write, true)
+            .expect("Failed to batch-write to database in resize");
+        self.set_length(new_len);
+    }
+
     /// Create a new, empty database vector
     pub fn new(db: DB) -> Self {
         let mut ret = DatabaseVector {
             db,
             _type: PhantomData,
+            _codec: PhantomData,
+            _index: PhantomData,
+            cache: HashMap::new(),
+            pending_pushes: Vec::new(),
+            read_cache: None,
         };
         // TODO: It might be possible to check this more rigorously using a DBIterator
         assert!(
             ret.attempt_verify_empty(),
             "Database must be empty when instantiating database vector with `new`"
         );
-        ret.set_length(0);
+        ret.set_length(I::ZERO);
+        ret.set_schema_version(CURRENT_SCHEMA_VERSION);
 
         ret
     }
 
-    pub fn get(&mut self, index: IndexType) -> T {
-        debug_assert!(
-            self.len() > index,
-            "Cannot get outside of length. Length: {}, index: {}",
-            self.len(),
-            index
-        );
-        let index_bytes: Vec<u8> = bincode::serialize(&index).unwrap();
-        let elem_as_bytes = self.db.get(&index_bytes).unwrap();
-        bincode::deserialize(&elem_as_bytes).unwrap()
+    pub fn try_get(&mut self, index: I) -> Result<T, DatabaseVectorError<I>> {
+        let length = self.try_len()?;
+        if index >= length {
+            return Err(DatabaseVectorError::IndexOutOfBounds { index, length });
+        }
+
+        let persisted_length = length - I::from_usize(self.pending_pushes.len());
+        if index >= persisted_length {
+            let pending_index = (index - persisted_length).to_usize();
+            return Ok(self.pending_pushes[pending_index].clone());
+        }
+        if let Some(value) = self.cache.get(&index) {
+            return Ok(value.clone());
+        }
+        if let Some(value) = self.read_cache.as_mut().and_then(|c| c.get(&index)) {
+            return Ok(value.clone());
+        }
+
+        let elem_as_bytes = self
+            .db
+            .get(&index.to_be_bytes_vec())
+            .ok_or(DatabaseVectorError::IndexOutOfBounds { index, length })?;
+        let value = C::decode_value(&elem_as_bytes);
+        if let Some(read_cache) = &mut self.read_cache {
+            read_cache.put(index, value.clone());
+        }
+        Ok(value)
     }
 
-    pub fn set(&mut self, index: IndexType, value: T) {
-        debug_assert!(
-            self.len() > index,
-            "Cannot set outside of length. Length: {}, index: {}",
-            self.len(),
-            index
-        );
-        let index_bytes: Vec<u8> = bincode::serialize(&index).unwrap();
-        let value_bytes: Vec<u8> = bincode::serialize(&value).unwrap();
-        self.db.put(&index_bytes, &value_bytes).unwrap();
+    pub fn get(&mut self, index: I) -> T {
+        match self.try_get(index) {
+            Ok(value) => value,
+            Err(DatabaseVectorError::IndexOutOfBounds { index, length }) => {
+                panic!("Cannot get outside of length. Length: {length}, index: {index}")
+            }
+            Err(e) => panic!("Failed to get element {index} from database vector: {e}"),
+        }
+    }
+
+    /// Look up several, possibly non-contiguous, indices with individual point lookups. The
+    /// read-side counterpart of [`Self::batch_set`].
+    pub fn get_many(&mut self, indices: &[I]) -> Vec<T> {
+        indices.iter().map(|&index| self.get(index)).collect()
+    }
+
+    /// Read a contiguous range of indices via a single LevelDB scan starting at `range.start`,
+    /// rather than `range.len()` individual point lookups. The scan is not bounded by
+    /// `range.end`: it decodes every element from `range.start` to the end of the vector and keeps
+    /// the first `range.len()` of them.
+    pub fn get_range(&mut self, range: std::ops::Range<I>) -> Vec<T> {
+        self.iter_from(range.start)
+            .take(range.end.saturating_sub(range.start).to_usize())
+            .collect()
+    }
+
+    pub fn try_set(&mut self, index: I, value: T) -> Result<(), DatabaseVectorError<I>> {
+        let length = self.try_len()?;
+        if index >= length {
+            return Err(DatabaseVectorError::IndexOutOfBounds { index, length });
+        }
+
+        let persisted_length = length - I::from_usize(self.pending_pushes.len());
+        if index >= persisted_length {
+            let pending_index = (index - persisted_length).to_usize();
+            self.pending_pushes[pending_index] = value;
+        } else {
+            self.cache.insert(index, value);
+            if let Some(read_cache) = &mut self.read_cache {
+                read_cache.invalidate(&index);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn set(&mut self, index: I, value: T) {
+        match self.try_set(index, value) {
+            Ok(()) => {}
+            Err(DatabaseVectorError::IndexOutOfBounds { index, length }) => {
+                panic!("Cannot set outside of length. Length: {length}, index: {index}")
+            }
+            Err(e) => panic!("Failed to set element {index} on database vector: {e}"),
+        }
     }
 
-    pub fn batch_set(&mut self, indices_and_vals: &[(IndexType, T)]) {
-        let indices: Vec<IndexType> = indices_and_vals.iter().map(|(index, _)| *index).collect();
+    pub fn batch_set(&mut self, indices_and_vals: &[(I, T)]) {
+        self.persist();
+        self.invalidate_read_cache();
+        let indices: Vec<I> = indices_and_vals.iter().map(|(index, _)| *index).collect();
         let length = self.len();
         assert!(
             indices.iter().all(|index| *index < length),
@@ -136,9 +488,8 @@ impl<T: Serialize + DeserializeOwned> DatabaseVector<T> {
         );
         let mut batch_write = WriteBatch::new();
         for (index, val) in indices_and_vals.iter() {
-            let index_bytes: Vec<u8> = bincode::serialize(index).unwrap();
-            let value_bytes: Vec<u8> = bincode::serialize(val).unwrap();
-            batch_write.put(&index_bytes, &value_bytes);
+            let value_bytes: Vec<u8> = C::encode_value(val);
+            batch_write.put(&index.to_be_bytes_vec(), &value_bytes);
         }
 
         self.db
@@ -147,29 +498,254 @@ impl<T: Serialize + DeserializeOwned> DatabaseVector<T> {
     }
 
     pub fn pop(&mut self) -> Option<T> {
-        match self.len() {
-            0 => None,
-            length => {
-                let element = self.get(length - 1);
-                self.delete(length - 1);
-                self.set_length(length - 1);
-                Some(element)
-            }
+        self.persist();
+        let length = self.len();
+        if length == I::ZERO {
+            return None;
+        }
+
+        let last_index = length - I::ONE;
+        let element = self.get(last_index);
+        self.delete(last_index);
+        self.set_length(last_index);
+        Some(element)
+    }
+
+    /// Pop up to `n` elements off the back in a single write batch, rather than `n` individual
+    /// [`Self::pop`] calls. Returns fewer than `n` elements if the vector holds less than that,
+    /// same as calling `pop()` in a loop until it returns `None` would. Elements come back in the
+    /// order `pop()` would return them: most-recently-pushed first.
+    pub fn pop_many(&mut self, n: usize) -> Vec<T> {
+        self.persist();
+        let old_length = self.len();
+        let n = n.min(old_length.to_usize());
+        let new_length = old_length.saturating_sub(I::from_usize(n));
+
+        let mut popped = Vec::with_capacity(n);
+        let mut batch_write = WriteBatch::new();
+        let mut index = old_length;
+        while index > new_length {
+            index = index - I::ONE;
+            popped.push(self.get(index));
+            batch_write.delete(&index.to_be_bytes_vec());
+        }
+
+        if n > 0 {
+            batch_write.put(&LENGTH_KEY, &new_length.to_be_bytes_vec());
+            self.db
+                .write(batch_write, true)
+                .expect("Failed to batch-write to database in pop_many");
+            self.invalidate_read_cache();
+        }
+
+        popped
+    }
+
+    /// Insert `value` at `index`, shifting every element at `index` and beyond up by one.
+    /// O(len() - index): the shifted tail is rewritten in a single batch, rather than reading and
+    /// rewriting the whole vector or moving one element at a time. Panics if `index > len()`.
+    pub fn insert(&mut self, index: I, value: T) {
+        self.persist();
+        self.invalidate_read_cache();
+        let old_length = self.len();
+        assert!(
+            index <= old_length,
+            "Cannot insert beyond the end of the vector. Length: {old_length}, index: {index}"
+        );
+
+        let mut batch_write = WriteBatch::new();
+        let mut i = old_length;
+        while i > index {
+            i = i - I::ONE;
+            let moved = self.get(i);
+            let value_bytes: Vec<u8> = C::encode_value(&moved);
+            batch_write.put(&(i + I::ONE).to_be_bytes_vec(), &value_bytes);
+        }
+        let value_bytes: Vec<u8> = C::encode_value(&value);
+        batch_write.put(&index.to_be_bytes_vec(), &value_bytes);
+
+        let new_length = old_length + I::ONE;
+        batch_write.put(&LENGTH_KEY, &new_length.to_be_bytes_vec());
+
+        self.db
+            .write(batch_write, true)
+            .expect("Failed to batch-write to database in insert");
+    }
+
+    /// Remove and return the element at `index`, shifting every later element down by one.
+    /// O(len() - index): the shifted tail is rewritten in a single batch, rather than reading and
+    /// rewriting the whole vector or moving one element at a time. Panics if `index >= len()`.
+    pub fn remove(&mut self, index: I) -> T {
+        self.persist();
+        self.invalidate_read_cache();
+        let old_length = self.len();
+        assert!(
+            index < old_length,
+            "Cannot remove outside of length. Length: {old_length}, index: {index}"
+        );
+
+        let removed = self.get(index);
+
+        let mut batch_write = WriteBatch::new();
+        let last_index = old_length - I::ONE;
+        let mut i = index;
+        while i < last_index {
+            let moved = self.get(i + I::ONE);
+            let value_bytes: Vec<u8> = C::encode_value(&moved);
+            batch_write.put(&i.to_be_bytes_vec(), &value_bytes);
+            i = i + I::ONE;
         }
+        batch_write.delete(&last_index.to_be_bytes_vec());
+        batch_write.put(&LENGTH_KEY, &last_index.to_be_bytes_vec());
+
+        self.db
+            .write(batch_write, true)
+            .expect("Failed to batch-write to database in remove");
+
+        removed
+    }
+
+    pub fn try_push(&mut self, value: T) -> Result<(), DatabaseVectorError<I>> {
+        self.pending_pushes.push(value);
+        Ok(())
     }
 
     pub fn push(&mut self, value: T) {
-        let length = self.len();
-        let index_bytes = bincode::serialize(&length).unwrap();
-        let value_bytes = bincode::serialize(&value).unwrap();
-        self.db.put(&index_bytes, &value_bytes).unwrap();
-        self.set_length(length + 1);
+        self.try_push(value)
+            .expect("Pushing element to database vector must succeed");
+    }
+
+    /// Push every element of `values` in a single write batch, the batched counterpart to
+    /// [`Self::push`] (and to [`Self::pop_many`]). Equivalent to [`Self::extend`], just accepting
+    /// an already-materialized slice, for callers appending a batch of leaves they already hold
+    /// as a `&[T]` rather than an owned iterator.
+    pub fn push_many(&mut self, values: &[T]) {
+        self.extend(values.iter().cloned());
+    }
+
+    /// Append every item from `values` in a single write batch, including the updated length,
+    /// instead of one `push` call per item. Any writes already buffered by `set`/`push` are
+    /// persisted first, so `values` land at contiguous indices right after the vector's current
+    /// length.
+    pub fn extend(&mut self, values: impl IntoIterator<Item = T>) {
+        self.persist();
+        let mut length = self.len();
+
+        let mut batch_write = WriteBatch::new();
+        for value in values {
+            let value_bytes: Vec<u8> = C::encode_value(&value);
+            batch_write.put(&length.to_be_bytes_vec(), &value_bytes);
+            length = length + I::ONE;
+        }
+        batch_write.put(&LENGTH_KEY, &length.to_be_bytes_vec());
+
+        self.db
+            .write(batch_write, true)
+            .expect("Failed to batch-write to database in extend");
     }
 
     /// Dispose of the vector and return the database. You should probably only use this for testing.
     pub fn extract_db(self) -> DB {
         self.db
     }
+
+    /// Dump every element to `path` as a compact, versioned file: a format-version tag, the
+    /// element count, and then each element's [`ValueCodec::encode_value`] bytes, length-prefixed
+    /// since the codec's output size need not be fixed. Meant for backups and for migrating a
+    /// vector's contents into a differently-configured [`DatabaseVector`] (e.g. a different
+    /// backend, a different `C`, or a different index type `I`) via [`Self::import_from_file`].
+    ///
+    /// The element count is always written as a `u64`, independent of `I`, so dumps stay portable
+    /// across `DatabaseVector`s that differ only in index type.
+    pub fn export_to_file(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        self.persist();
+        let length = self.len();
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(&EXPORT_FORMAT_VERSION.to_le_bytes())?;
+        writer.write_all(&(length.to_usize() as u64).to_le_bytes())?;
+        for value in self.iter() {
+            let value_bytes = C::encode_value(&value);
+            writer.write_all(&(value_bytes.len() as u64).to_le_bytes())?;
+            writer.write_all(&value_bytes)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Populate a fresh, empty `db` from a dump written by [`Self::export_to_file`].
+    pub fn import_from_file(db: DB, path: impl AsRef<Path>) -> Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+
+        let mut version_bytes = [0u8; 4];
+        reader.read_exact(&mut version_bytes)?;
+        let version = u32::from_le_bytes(version_bytes);
+        if version != EXPORT_FORMAT_VERSION {
+            bail!("Unsupported database vector export format version: {version}");
+        }
+
+        let mut length_bytes = [0u8; 8];
+        reader.read_exact(&mut length_bytes)?;
+        let length = u64::from_le_bytes(length_bytes);
+
+        let mut vector = Self::new(db);
+        for _ in 0..length {
+            let mut value_len_bytes = [0u8; 8];
+            reader.read_exact(&mut value_len_bytes)?;
+            let value_len = u64::from_le_bytes(value_len_bytes) as usize;
+
+            let mut value_bytes = vec![0u8; value_len];
+            reader.read_exact(&mut value_bytes)?;
+            vector.push(C::decode_value(&value_bytes));
+        }
+        vector.persist();
+
+        Ok(vector)
+    }
+
+    /// Iterate over every element, via a single LevelDB scan rather than [`Self::len`] individual
+    /// [`Self::get`] point lookups.
+    pub fn iter(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.iter_from(I::ZERO)
+    }
+
+    /// Iterate over the elements from `start_index` onwards, via a single LevelDB scan.
+    ///
+    /// Indices are encoded as fixed-width big-endian bytes, so raw key-byte order matches numeric
+    /// index order. This lets the scan `seek` straight to `start_index` and walk forward, rather
+    /// than buffering and sorting every entry the way a little-endian encoding would require.
+    pub fn iter_from(&mut self, start_index: I) -> impl Iterator<Item = T> + '_ {
+        self.persist();
+        let mut db_iterator = self
+            .db
+            .new_iter()
+            .expect("Opening a LevelDB iterator must succeed");
+        db_iterator.seek(&start_index.to_be_bytes_vec());
+
+        let mut items = Vec::new();
+        let mut key = Vec::new();
+        let mut value = Vec::new();
+        while db_iterator.valid() {
+            db_iterator.current(&mut key, &mut value);
+            if key != LENGTH_KEY && key != SCHEMA_VERSION_KEY {
+                items.push(C::decode_value(&value));
+            }
+            if !db_iterator.advance() {
+                break;
+            }
+        }
+        items.into_iter()
+    }
+}
+
+impl<T: Clone, C: ValueCodec<T>, I: VectorIndex> IntoIterator for DatabaseVector<T, C, I> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        let values: Vec<T> = self.iter().collect();
+        values.into_iter()
+    }
 }
 
 #[cfg(test)]
@@ -298,6 +874,210 @@ mod database_vector_tests {
         assert!(new_db_vector.is_empty());
     }
 
+    #[test]
+    fn iter_visits_every_element_in_index_order() {
+        let mut db_vector = test_constructor();
+        let values: Vec<u64> = (0..10).map(|i| i * 11).collect();
+        for &value in &values {
+            db_vector.push(value);
+        }
+
+        assert_eq!(values, db_vector.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn iter_from_skips_the_leading_elements() {
+        let mut db_vector = test_constructor();
+        let values: Vec<u64> = (0..10).map(|i| i * 11).collect();
+        for &value in &values {
+            db_vector.push(value);
+        }
+
+        assert_eq!(values[3..], db_vector.iter_from(3).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn iter_returns_elements_in_numeric_index_order_past_256() {
+        // Regression test for the pre-big-endian encoding, where indices past 256 sorted out of
+        // numeric order as raw key bytes. Big-endian, fixed-width keys make this trivially true,
+        // but it's cheap enough to keep pinned down.
+        let mut db_vector = test_constructor();
+        let values: Vec<u64> = (0..300).collect();
+        for &value in &values {
+            db_vector.push(value);
+        }
+
+        assert_eq!(values, db_vector.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn into_iter_consumes_the_vector_in_index_order() {
+        let mut db_vector = test_constructor();
+        let values: Vec<u64> = (0..10).map(|i| i * 11).collect();
+        for &value in &values {
+            db_vector.push(value);
+        }
+
+        assert_eq!(values, db_vector.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn try_get_and_try_set_report_index_out_of_bounds_instead_of_panicking() {
+        let mut db_vector = test_constructor();
+        db_vector.push(5558999);
+
+        assert_eq!(
+            Err(DatabaseVectorError::IndexOutOfBounds {
+                index: 1,
+                length: 1
+            }),
+            db_vector.try_get(1)
+        );
+        assert_eq!(
+            Err(DatabaseVectorError::IndexOutOfBounds {
+                index: 1,
+                length: 1
+            }),
+            db_vector.try_set(1, 14)
+        );
+        assert_eq!(Ok(5558999), db_vector.try_get(0));
+    }
+
+    #[test]
+    fn try_push_and_try_len_agree_with_their_panicking_counterparts() {
+        let mut db_vector = test_constructor();
+        assert_eq!(Ok(0), db_vector.try_len());
+
+        db_vector.try_push(17).unwrap();
+        assert_eq!(Ok(1), db_vector.try_len());
+        assert_eq!(Ok(17), db_vector.try_get(0));
+    }
+
+    #[test]
+    fn set_and_push_are_buffered_until_persist() {
+        let mut db_vector = test_constructor();
+        db_vector.push(1);
+        db_vector.push(2);
+        db_vector.persist();
+        assert!(!db_vector.is_dirty());
+
+        db_vector.set(0, 100);
+        db_vector.push(3);
+        assert!(db_vector.is_dirty());
+
+        // The overlay is visible through `get`/`len` before persisting...
+        assert_eq!(100, db_vector.get(0));
+        assert_eq!(3, db_vector.len());
+        assert_eq!(3, db_vector.get(2));
+
+        // ...and still there, unchanged, after persisting to the database.
+        db_vector.persist();
+        assert!(!db_vector.is_dirty());
+        assert_eq!(vec![100, 2, 3], db_vector.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn persist_is_a_no_op_when_nothing_is_buffered() {
+        let mut db_vector = test_constructor();
+        db_vector.push(17);
+        db_vector.persist();
+        db_vector.persist();
+        assert_eq!(1, db_vector.len());
+        assert_eq!(17, db_vector.get(0));
+    }
+
+    #[test]
+    fn get_many_reads_arbitrary_indices() {
+        let mut db_vector = test_constructor();
+        for i in 0..10u64 {
+            db_vector.push(i * 11);
+        }
+
+        assert_eq!(vec![0, 33, 99], db_vector.get_many(&[0, 3, 9]));
+    }
+
+    #[test]
+    fn bfield_codec_backed_vector_persists_values_via_their_bfieldcodec_encoding() {
+        use super::super::database_vector_codec::BFieldValueCodec;
+        use crate::shared_math::b_field_element::BFieldElement;
+
+        let opt = rusty_leveldb::in_memory();
+        let db = DB::open("mydatabase", opt).unwrap();
+        let mut db_vector: DatabaseVector<BFieldElement, BFieldValueCodec> =
+            DatabaseVector::new(db);
+
+        db_vector.push(BFieldElement::new(42));
+        db_vector.push(BFieldElement::new(1337));
+        assert_eq!(BFieldElement::new(42), db_vector.get(0));
+        assert_eq!(BFieldElement::new(1337), db_vector.get(1));
+
+        db_vector.persist();
+        assert_eq!(
+            vec![BFieldElement::new(42), BFieldElement::new(1337)],
+            db_vector.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn get_range_reads_a_contiguous_slice() {
+        let mut db_vector = test_constructor();
+        let values: Vec<u64> = (0..10).map(|i| i * 11).collect();
+        for &value in &values {
+            db_vector.push(value);
+        }
+
+        assert_eq!(values[2..5], db_vector.get_range(2..5));
+        assert_eq!(values[..], db_vector.get_range(0..10));
+        assert_eq!(Vec::<u64>::new(), db_vector.get_range(4..4));
+    }
+
+    #[test]
+    fn truncate_deletes_trailing_elements() {
+        let mut db_vector = test_constructor();
+        for i in 0..10 {
+            db_vector.push(i);
+        }
+
+        db_vector.truncate(4);
+        assert_eq!(4, db_vector.len());
+        assert_eq!(vec![0, 1, 2, 3], db_vector.iter().collect::<Vec<_>>());
+
+        // Truncating to a length that is not shorter than the current one is a no-op.
+        db_vector.truncate(4);
+        assert_eq!(4, db_vector.len());
+        db_vector.truncate(100);
+        assert_eq!(4, db_vector.len());
+    }
+
+    #[test]
+    fn clear_empties_the_vector() {
+        let mut db_vector = test_constructor();
+        for i in 0..10 {
+            db_vector.push(i);
+        }
+
+        db_vector.clear();
+        assert!(db_vector.is_empty());
+        assert_eq!(0, db_vector.len());
+    }
+
+    #[test]
+    fn resize_grows_and_shrinks() {
+        let mut db_vector = test_constructor();
+        for i in 0..3 {
+            db_vector.push(i);
+        }
+
+        db_vector.resize(6, 42);
+        assert_eq!(
+            vec![0, 1, 2, 42, 42, 42],
+            db_vector.iter().collect::<Vec<_>>()
+        );
+
+        db_vector.resize(2, 42);
+        assert_eq!(vec![0, 1], db_vector.iter().collect::<Vec<_>>());
+    }
+
     #[test]
     fn index_zero_test() {
         // Verify that index zero does not overwrite the stored length
@@ -309,4 +1089,326 @@ mod database_vector_tests {
         assert_eq!(0, db_vector.len());
         assert!(db_vector.is_empty());
     }
+
+    fn unique_temp_file_path(test_name: &str) -> std::path::PathBuf {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let unique = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!(
+            "twenty_first_db_vector_export_{test_name}_{}_{unique}.bin",
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn export_to_file_and_import_from_file_round_trip() {
+        let mut db_vector = test_constructor();
+        for i in 0..50u64 {
+            db_vector.push(i * i);
+        }
+
+        let path = unique_temp_file_path("round_trip");
+        db_vector.export_to_file(&path).unwrap();
+
+        let opt = rusty_leveldb::in_memory();
+        let fresh_db = DB::open("mydatabase", opt).unwrap();
+        let mut imported: DatabaseVector<u64> =
+            DatabaseVector::import_from_file(fresh_db, &path).unwrap();
+
+        assert_eq!(
+            db_vector.iter().collect::<Vec<_>>(),
+            imported.iter().collect::<Vec<_>>()
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn import_from_file_rejects_a_dump_with_an_unrecognized_format_version() {
+        let path = unique_temp_file_path("bad_version");
+        std::fs::write(&path, 999u32.to_le_bytes()).unwrap();
+
+        let opt = rusty_leveldb::in_memory();
+        let db = DB::open("mydatabase", opt).unwrap();
+        let result: Result<DatabaseVector<u64>> = DatabaseVector::import_from_file(db, &path);
+        assert!(result.is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn stats_reports_element_count_byte_size_and_pending_writes() {
+        let mut db_vector = test_constructor();
+        for i in 0..10u64 {
+            db_vector.push(i);
+        }
+        db_vector.persist();
+
+        let stats = db_vector.stats();
+        assert_eq!(10, stats.element_count);
+        assert_eq!(0, stats.pending_writes);
+        assert!(stats.total_value_bytes > 0);
+
+        db_vector.push(99);
+        db_vector.set(0, 100);
+        let stats = db_vector.stats();
+        assert_eq!(2, stats.pending_writes);
+    }
+
+    #[test]
+    fn compact_range_does_not_change_the_vectors_contents() {
+        let mut db_vector = test_constructor();
+        for i in 0..20u64 {
+            db_vector.push(i);
+        }
+
+        db_vector.compact_range();
+        assert_eq!(
+            (0..20).collect::<Vec<_>>(),
+            db_vector.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn insert_shifts_the_tail_up_by_one() {
+        let mut db_vector = test_constructor();
+        db_vector.extend([0, 1, 2, 3]);
+
+        db_vector.insert(2, 99);
+
+        assert_eq!(5, db_vector.len());
+        assert_eq!(vec![0, 1, 99, 2, 3], db_vector.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn insert_at_the_end_behaves_like_push() {
+        let mut db_vector = test_constructor();
+        db_vector.extend([0, 1, 2]);
+
+        db_vector.insert(3, 42);
+
+        assert_eq!(vec![0, 1, 2, 42], db_vector.iter().collect::<Vec<_>>());
+    }
+
+    #[should_panic = "Cannot insert beyond the end of the vector. Length: 2, index: 3"]
+    #[test]
+    fn insert_beyond_the_end_panics() {
+        let mut db_vector = test_constructor();
+        db_vector.extend([0, 1]);
+        db_vector.insert(3, 42);
+    }
+
+    #[test]
+    fn remove_shifts_the_tail_down_by_one_and_returns_the_removed_element() {
+        let mut db_vector = test_constructor();
+        db_vector.extend([0, 1, 2, 3]);
+
+        assert_eq!(1, db_vector.remove(1));
+        assert_eq!(3, db_vector.len());
+        assert_eq!(vec![0, 2, 3], db_vector.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn remove_the_last_element_behaves_like_pop() {
+        let mut db_vector = test_constructor();
+        db_vector.extend([0, 1, 2]);
+
+        assert_eq!(2, db_vector.remove(2));
+        assert_eq!(vec![0, 1], db_vector.iter().collect::<Vec<_>>());
+    }
+
+    #[should_panic = "Cannot remove outside of length. Length: 2, index: 2"]
+    #[test]
+    fn remove_out_of_bounds_panics() {
+        let mut db_vector = test_constructor();
+        db_vector.extend([0, 1]);
+        db_vector.remove(2);
+    }
+
+    #[test]
+    fn push_many_appends_every_item_at_contiguous_indices() {
+        let mut db_vector = test_constructor();
+        db_vector.push(1);
+        db_vector.push(2);
+
+        db_vector.push_many(&[3, 4, 5]);
+
+        assert_eq!(5, db_vector.len());
+        assert_eq!(vec![1, 2, 3, 4, 5], db_vector.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn pop_many_returns_elements_most_recently_pushed_first() {
+        let mut db_vector = test_constructor();
+        db_vector.extend([0, 1, 2, 3, 4]);
+
+        assert_eq!(vec![4, 3, 2], db_vector.pop_many(3));
+        assert_eq!(2, db_vector.len());
+        assert_eq!(vec![0, 1], db_vector.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn pop_many_caps_at_the_vectors_length_instead_of_panicking() {
+        let mut db_vector = test_constructor();
+        db_vector.extend([0, 1]);
+
+        assert_eq!(vec![1, 0], db_vector.pop_many(10));
+        assert!(db_vector.is_empty());
+        assert_eq!(Vec::<u64>::new(), db_vector.pop_many(5));
+    }
+
+    #[test]
+    fn extend_appends_every_item_at_contiguous_indices() {
+        let mut db_vector = test_constructor();
+        db_vector.push(1);
+        db_vector.push(2);
+
+        db_vector.extend(vec![3, 4, 5]);
+
+        assert_eq!(5, db_vector.len());
+        assert_eq!(vec![1, 2, 3, 4, 5], db_vector.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn extend_on_an_empty_vector_behaves_like_a_fresh_push_sequence() {
+        let mut db_vector = test_constructor();
+        db_vector.extend(0..10u64);
+
+        assert_eq!(10, db_vector.len());
+        assert_eq!(
+            (0..10).collect::<Vec<_>>(),
+            db_vector.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn new_vectors_are_created_at_the_current_schema_version() {
+        let mut db_vector = test_constructor();
+        assert_eq!(CURRENT_SCHEMA_VERSION, db_vector.schema_version());
+    }
+
+    #[test]
+    fn restoring_a_pre_versioning_database_reports_schema_version_zero() {
+        let mut db_vector = test_constructor();
+        db_vector.db.delete(&SCHEMA_VERSION_KEY).unwrap();
+
+        let mut restored: DatabaseVector<u64> = DatabaseVector::restore(db_vector.extract_db());
+        assert_eq!(0, restored.schema_version());
+    }
+
+    #[test]
+    fn migrate_runs_the_closure_once_and_advances_the_schema_version() {
+        let mut db_vector = test_constructor();
+        db_vector.set_schema_version(0);
+        db_vector.push(21);
+        db_vector.persist();
+
+        let mut run_count = 0;
+        for _ in 0..2 {
+            db_vector.migrate(0, |v| {
+                run_count += 1;
+                let doubled = v.get(0) * 2;
+                v.set(0, doubled);
+            });
+        }
+
+        assert_eq!(1, run_count);
+        assert_eq!(42, db_vector.get(0));
+        assert_eq!(1, db_vector.schema_version());
+    }
+
+    #[test]
+    fn migrate_ignores_a_from_version_that_does_not_match_the_current_one() {
+        let mut db_vector = test_constructor();
+        assert_eq!(CURRENT_SCHEMA_VERSION, db_vector.schema_version());
+
+        let mut ran = false;
+        db_vector.migrate(0, |_| ran = true);
+
+        assert!(!ran);
+        assert_eq!(CURRENT_SCHEMA_VERSION, db_vector.schema_version());
+    }
+
+    #[test]
+    fn a_u32_indexed_vector_behaves_like_the_default_u64_indexed_one() {
+        let opt = rusty_leveldb::in_memory();
+        let db = DB::open("mydatabase", opt).unwrap();
+        let mut db_vector: DatabaseVector<u64, BincodeCodec, u32> = DatabaseVector::new(db);
+
+        for i in 0..300u64 {
+            db_vector.push(i);
+        }
+        db_vector.insert(10, 999);
+        assert_eq!(999, db_vector.remove(10));
+
+        assert_eq!(300u32, db_vector.len());
+        assert_eq!(
+            (0..300).collect::<Vec<_>>(),
+            db_vector.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn read_cache_serves_a_repeated_get_without_going_stale_after_a_set() {
+        let mut db_vector = test_constructor();
+        for i in 0..10u64 {
+            db_vector.push(i * i);
+        }
+        db_vector.persist();
+
+        db_vector.enable_read_cache(4);
+        assert_eq!(9, db_vector.get(3));
+        assert_eq!(9, db_vector.get(3));
+
+        db_vector.set(3, 999);
+        assert_eq!(999, db_vector.get(3));
+        db_vector.persist();
+        assert_eq!(999, db_vector.get(3));
+    }
+
+    #[test]
+    fn pop_invalidates_the_read_cache_entry_for_the_popped_index() {
+        let mut db_vector = test_constructor();
+        db_vector.extend([1, 2, 3]);
+        db_vector.persist();
+
+        db_vector.enable_read_cache(4);
+        assert_eq!(3, db_vector.get(2));
+        assert_eq!(Some(3), db_vector.pop());
+
+        db_vector.extend([42]);
+        db_vector.persist();
+        assert_eq!(42, db_vector.get(2));
+    }
+
+    #[test]
+    fn disable_read_cache_falls_back_to_reading_straight_from_the_database() {
+        let mut db_vector = test_constructor();
+        db_vector.extend([1, 2, 3]);
+        db_vector.persist();
+
+        db_vector.enable_read_cache(4);
+        assert_eq!(2, db_vector.get(1));
+        db_vector.disable_read_cache();
+
+        db_vector.set(1, 200);
+        db_vector.persist();
+        assert_eq!(200, db_vector.get(1));
+    }
+
+    #[test]
+    fn a_u128_indexed_vector_behaves_like_the_default_u64_indexed_one() {
+        let opt = rusty_leveldb::in_memory();
+        let db = DB::open("mydatabase", opt).unwrap();
+        let mut db_vector: DatabaseVector<u64, BincodeCodec, u128> = DatabaseVector::new(db);
+
+        for i in 0..10u64 {
+            db_vector.push(i * i);
+        }
+
+        assert_eq!(10u128, db_vector.len());
+        assert_eq!(
+            vec![0, 1, 4, 9, 16, 25, 36, 49, 64, 81],
+            db_vector.iter().collect::<Vec<_>>()
+        );
+    }
 }
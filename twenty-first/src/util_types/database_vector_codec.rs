@@ -0,0 +1,84 @@
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::shared_math::b_field_element::BFieldElement;
+use crate::shared_math::bfield_codec::BFieldCodec;
+
+/// How a [`super::database_vector::DatabaseVector`] turns its elements into the bytes actually
+/// written to the database, and back. Selected via `DatabaseVector`'s codec type parameter.
+pub trait ValueCodec<T> {
+    fn encode_value(value: &T) -> Vec<u8>;
+    fn decode_value(bytes: &[u8]) -> T;
+}
+
+/// The default codec, used unless a `DatabaseVector` is explicitly parameterized otherwise.
+/// Works for any `T: Serialize + DeserializeOwned`, at the cost of the on-disk bytes having
+/// nothing to do with `T`'s canonical in-proof encoding, if it has one.
+pub struct BincodeCodec;
+
+impl<T: Serialize + DeserializeOwned> ValueCodec<T> for BincodeCodec {
+    fn encode_value(value: &T) -> Vec<u8> {
+        bincode::serialize(value).unwrap()
+    }
+
+    fn decode_value(bytes: &[u8]) -> T {
+        bincode::deserialize(bytes).unwrap()
+    }
+}
+
+/// Stores values via their [`BFieldCodec`] encoding instead of `bincode`. The bytes on disk are
+/// then the same sequence of `BFieldElement`s the type would have inside a proof, so a stored
+/// value can be hashed directly without first deserializing it into a Rust value.
+pub struct BFieldValueCodec;
+
+impl<T: BFieldCodec> ValueCodec<T> for BFieldValueCodec {
+    fn encode_value(value: &T) -> Vec<u8> {
+        value
+            .encode()
+            .iter()
+            .flat_map(|bfe| bfe.value().to_le_bytes())
+            .collect()
+    }
+
+    fn decode_value(bytes: &[u8]) -> T {
+        let bfes: Vec<BFieldElement> = bytes
+            .chunks_exact(8)
+            .map(|chunk| BFieldElement::new(u64::from_le_bytes(chunk.try_into().unwrap())))
+            .collect();
+        *T::decode(&bfes).expect("Stored BFieldCodec-encoded value must decode")
+    }
+}
+
+#[cfg(test)]
+mod database_vector_codec_tests {
+    use super::*;
+
+    #[test]
+    fn bincode_codec_round_trips_a_serde_only_type() {
+        let value: Vec<u64> = vec![1, 2, 3];
+        let encoded = BincodeCodec::encode_value(&value);
+        assert_eq!(
+            value,
+            <BincodeCodec as ValueCodec<Vec<u64>>>::decode_value(&encoded)
+        );
+    }
+
+    #[test]
+    fn bfield_value_codec_round_trips_a_bfieldcodec_type() {
+        let value = BFieldElement::new(1337);
+        let encoded = BFieldValueCodec::encode_value(&value);
+        assert_eq!(
+            value,
+            <BFieldValueCodec as ValueCodec<BFieldElement>>::decode_value(&encoded)
+        );
+    }
+
+    #[test]
+    fn bfield_value_codec_round_trips_a_vec_of_bfieldcodec_values() {
+        let value: Vec<BFieldElement> = (0..20).map(BFieldElement::new).collect();
+        let encoded = BFieldValueCodec::encode_value(&value);
+        assert_eq!(
+            value,
+            <BFieldValueCodec as ValueCodec<Vec<BFieldElement>>>::decode_value(&encoded)
+        );
+    }
+}
@@ -0,0 +1,95 @@
+/// The index/length type a [`super::database_vector::DatabaseVector`] is parameterized over.
+///
+/// Implemented for `u32`, `u64` (the default), and `u128`, so callers can pick the narrowest
+/// index width their vector actually needs: a `u32`-indexed vector uses 4-byte keys instead of
+/// 8, and a `u128`-indexed one can outgrow `u64::MAX` elements. Keys are encoded big-endian and
+/// fixed-width, so raw key-byte order matches numeric order, which is what lets
+/// [`super::database_vector::DatabaseVector::iter_from`] use a single forward LevelDB scan
+/// instead of buffering and sorting every entry.
+pub trait VectorIndex:
+    Copy
+    + Ord
+    + std::fmt::Debug
+    + std::fmt::Display
+    + std::hash::Hash
+    + std::ops::Add<Output = Self>
+    + std::ops::Sub<Output = Self>
+    + Send
+    + Sync
+    + 'static
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// Encode as fixed-width big-endian bytes, for use as a LevelDB key.
+    fn to_be_bytes_vec(self) -> Vec<u8>;
+
+    /// Decode from the bytes produced by [`Self::to_be_bytes_vec`].
+    fn from_be_bytes_slice(bytes: &[u8]) -> Self;
+
+    fn from_usize(n: usize) -> Self;
+
+    fn to_usize(self) -> usize;
+
+    fn saturating_sub(self, other: Self) -> Self;
+}
+
+macro_rules! impl_vector_index {
+    ($($t:ty),*) => {
+        $(
+            impl VectorIndex for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn to_be_bytes_vec(self) -> Vec<u8> {
+                    self.to_be_bytes().to_vec()
+                }
+
+                fn from_be_bytes_slice(bytes: &[u8]) -> Self {
+                    Self::from_be_bytes(bytes.try_into().expect("Index key has the wrong width"))
+                }
+
+                fn from_usize(n: usize) -> Self {
+                    n as Self
+                }
+
+                fn to_usize(self) -> usize {
+                    self as usize
+                }
+
+                fn saturating_sub(self, other: Self) -> Self {
+                    <$t>::saturating_sub(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_vector_index!(u32, u64, u128);
+
+#[cfg(test)]
+mod database_vector_index_tests {
+    use super::*;
+
+    fn round_trips_through_be_bytes<I: VectorIndex>(value: I) {
+        let bytes = value.to_be_bytes_vec();
+        assert_eq!(value, I::from_be_bytes_slice(&bytes));
+    }
+
+    #[test]
+    fn u32_u64_and_u128_round_trip_through_be_bytes() {
+        round_trips_through_be_bytes(0u32);
+        round_trips_through_be_bytes(u32::MAX);
+        round_trips_through_be_bytes(0u64);
+        round_trips_through_be_bytes(u64::MAX);
+        round_trips_through_be_bytes(0u128);
+        round_trips_through_be_bytes(u128::MAX);
+    }
+
+    #[test]
+    fn be_byte_order_matches_numeric_order() {
+        let low = 2u32.to_be_bytes_vec();
+        let high = 256u32.to_be_bytes_vec();
+        assert!(low < high, "big-endian encoding must sort numerically");
+    }
+}
@@ -0,0 +1,205 @@
+//! An append-only Merkle tree that supports [`IncrementalMerkleTree::append`] in `O(log n)`
+//! without rebuilding from scratch, for use cases where leaves arrive over time (e.g. an
+//! append-only log) and re-hashing every leaf on every insertion, as
+//! [`MerkleTree`](crate::util_types::merkle_tree::MerkleTree) would require, is prohibitive.
+//!
+//! The tree's shape follows the usual definition for streaming Merkle trees (as in Certificate
+//! Transparency's Merkle Tree Hash): the root over `n` leaves is defined recursively by splitting
+//! at the largest power of two `k < n`, `root(D[0..n]) = H(root(D[0..k]), root(D[k..n]))`, with
+//! `root(D[0..1]) = D[0]`. Appending one leaf only invalidates and recomputes the digests of
+//! complete subtrees on the current "right edge" of the tree, of which there are at most
+//! `log2(n)`; [`Self::frontier`] caches exactly those, one per power-of-two size, so both
+//! [`Self::append`] and [`Self::root`] only ever touch `O(log n)` cached digests.
+
+use std::marker::PhantomData;
+
+use crate::shared_math::digest::Digest;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
+
+/// The tree hash of the empty leaf sequence, matching the convention that an empty variable-
+/// length input hashes to a well-defined digest rather than being disallowed outright.
+fn empty_root<H: AlgebraicHasher>() -> Digest {
+    H::hash_varlen(&[])
+}
+
+#[derive(Debug, Clone)]
+pub struct IncrementalMerkleTree<H>
+where
+    H: AlgebraicHasher,
+{
+    num_leaves: u64,
+
+    /// `frontier[level]` is `Some(digest)` of the completed, rightmost subtree of `2^level`
+    /// leaves if bit `level` of `num_leaves` is set, i.e., such a subtree currently exists and
+    /// has not yet been merged into a larger one; otherwise `None`.
+    frontier: Vec<Option<Digest>>,
+    _hasher: PhantomData<H>,
+}
+
+impl<H> Default for IncrementalMerkleTree<H>
+where
+    H: AlgebraicHasher,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H> IncrementalMerkleTree<H>
+where
+    H: AlgebraicHasher,
+{
+    pub fn new() -> Self {
+        Self {
+            num_leaves: 0,
+            frontier: vec![],
+            _hasher: PhantomData,
+        }
+    }
+
+    pub fn num_leaves(&self) -> u64 {
+        self.num_leaves
+    }
+
+    /// Append `leaf`, merging it into the right-edge frontier. `O(log n)` in the number of
+    /// leaves already present: at most one [`AlgebraicHasher::hash_pair`] call per set bit of
+    /// `num_leaves`.
+    pub fn append(&mut self, leaf: Digest) {
+        let mut node = leaf;
+        let mut level = 0;
+        while level < self.frontier.len() && self.frontier[level].is_some() {
+            let left = self.frontier[level].take().unwrap();
+            node = H::hash_pair(&left, &node);
+            level += 1;
+        }
+
+        if level == self.frontier.len() {
+            self.frontier.push(Some(node));
+        } else {
+            self.frontier[level] = Some(node);
+        }
+        self.num_leaves += 1;
+    }
+
+    /// The current root, recomputed from the cached frontier in `O(log n)` without touching any
+    /// leaf that isn't on the right edge.
+    pub fn root(&self) -> Digest {
+        let mut accumulated: Option<Digest> = None;
+        for slot in &self.frontier {
+            let Some(subtree_root) = slot else {
+                continue;
+            };
+            accumulated = Some(match accumulated {
+                None => *subtree_root,
+                Some(right) => H::hash_pair(subtree_root, &right),
+            });
+        }
+        accumulated.unwrap_or_else(empty_root::<H>)
+    }
+}
+
+/// Compute the [`IncrementalMerkleTree`] root of `leaves` in `O(log n)` memory, without ever
+/// materializing the whole sequence: at most one [`Digest`] per level of the tree is held at a
+/// time, in the frontier of an [`IncrementalMerkleTree`] built up one leaf at a time. Useful for
+/// committing to a dataset that is streamed from somewhere that does not fit in RAM, e.g. an
+/// on-disk table too large to collect into a `Vec<Digest>` first.
+pub fn merkle_root_from_iter<H: AlgebraicHasher>(leaves: impl Iterator<Item = Digest>) -> Digest {
+    let mut tree = IncrementalMerkleTree::<H>::new();
+    for leaf in leaves {
+        tree.append(leaf);
+    }
+    tree.root()
+}
+
+#[cfg(test)]
+mod incremental_merkle_tree_tests {
+    use crate::shared_math::other::random_elements;
+    use crate::shared_math::tip5::Tip5;
+    use crate::util_types::merkle_tree::CpuParallel;
+    use crate::util_types::merkle_tree::MerkleTree;
+    use crate::util_types::merkle_tree_maker::MerkleTreeMaker;
+
+    use super::*;
+
+    #[test]
+    fn empty_tree_has_stable_root() {
+        let tree = IncrementalMerkleTree::<Tip5>::new();
+        assert_eq!(tree.root(), tree.root());
+        assert_eq!(0, tree.num_leaves());
+    }
+
+    #[test]
+    fn single_leaf_root_is_the_leaf_itself() {
+        let leaf: Digest = random_elements(1)[0];
+        let mut tree = IncrementalMerkleTree::<Tip5>::new();
+        tree.append(leaf);
+        assert_eq!(leaf, tree.root());
+    }
+
+    #[test]
+    fn root_matches_regular_merkle_tree_for_power_of_two_leaf_counts() {
+        let leaves: Vec<Digest> = random_elements(16);
+
+        let mut incremental = IncrementalMerkleTree::<Tip5>::new();
+        for &leaf in &leaves {
+            incremental.append(leaf);
+        }
+
+        let regular: MerkleTree<Tip5> = CpuParallel::from_digests(&leaves);
+        assert_eq!(regular.get_root(), incremental.root());
+    }
+
+    #[test]
+    fn root_changes_after_every_append() {
+        let leaves: Vec<Digest> = random_elements(10);
+        let mut tree = IncrementalMerkleTree::<Tip5>::new();
+        let mut previous_root = tree.root();
+
+        for &leaf in &leaves {
+            tree.append(leaf);
+            let new_root = tree.root();
+            assert_ne!(previous_root, new_root);
+            previous_root = new_root;
+        }
+        assert_eq!(leaves.len() as u64, tree.num_leaves());
+    }
+
+    #[test]
+    fn appending_in_two_batches_matches_appending_all_at_once() {
+        let leaves: Vec<Digest> = random_elements(13);
+
+        let mut incremental = IncrementalMerkleTree::<Tip5>::new();
+        for &leaf in &leaves {
+            incremental.append(leaf);
+        }
+
+        let mut in_batches = IncrementalMerkleTree::<Tip5>::new();
+        for &leaf in &leaves[..7] {
+            in_batches.append(leaf);
+        }
+        for &leaf in &leaves[7..] {
+            in_batches.append(leaf);
+        }
+
+        assert_eq!(incremental.root(), in_batches.root());
+    }
+
+    #[test]
+    fn merkle_root_from_iter_matches_incrementally_built_tree() {
+        let leaves: Vec<Digest> = random_elements(11);
+
+        let mut incremental = IncrementalMerkleTree::<Tip5>::new();
+        for &leaf in &leaves {
+            incremental.append(leaf);
+        }
+
+        let streamed_root = merkle_root_from_iter::<Tip5>(leaves.into_iter());
+        assert_eq!(incremental.root(), streamed_root);
+    }
+
+    #[test]
+    fn merkle_root_from_empty_iter_is_the_empty_root() {
+        let streamed_root = merkle_root_from_iter::<Tip5>(std::iter::empty());
+        assert_eq!(empty_root::<Tip5>(), streamed_root);
+    }
+}
@@ -0,0 +1,159 @@
+//! A tiny bounded LRU cache. Used by [`super::database_vector::DatabaseVector`]'s optional read
+//! cache, but written with no dependency on it, in case something else ever wants a bounded
+//! recency-ordered cache.
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Maps `K` to `V`, holding at most `capacity` entries. Once full, [`Self::put`] evicts whichever
+/// key was least recently touched by [`Self::get`] or [`Self::put`].
+///
+/// Recency is tracked with a plain `VecDeque`, so touching a key is `O(capacity)`. That's the
+/// right trade-off for the caches this is meant for (a few hundred to a few thousand hot
+/// indices): simple, allocation-free after warm-up, and not worth a doubly-linked-list-based
+/// `O(1)` implementation until a caller's capacity says otherwise.
+pub struct LruCache<K, V> {
+    capacity: usize,
+    entries: HashMap<K, V>,
+    /// Least-recently-touched key first, most-recently-touched key last.
+    recency: VecDeque<K>,
+}
+
+impl<K: Eq + Hash + Clone, V> LruCache<K, V> {
+    /// `capacity == 0` makes every `put` an immediate no-op-eviction, i.e. a cache that never
+    /// retains anything.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            recency: VecDeque::new(),
+        }
+    }
+
+    fn touch(&mut self, key: &K) {
+        if let Some(position) = self.recency.iter().position(|k| k == key) {
+            let touched = self.recency.remove(position).expect("Position just found");
+            self.recency.push_back(touched);
+        }
+    }
+
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        if !self.entries.contains_key(key) {
+            return None;
+        }
+        self.touch(key);
+        self.entries.get(key)
+    }
+
+    pub fn put(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.contains_key(&key) {
+            self.touch(&key);
+            self.entries.insert(key, value);
+            return;
+        }
+        if self.entries.len() >= self.capacity {
+            if let Some(oldest) = self.recency.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.recency.push_back(key.clone());
+        self.entries.insert(key, value);
+    }
+
+    pub fn invalidate(&mut self, key: &K) {
+        if self.entries.remove(key).is_some() {
+            if let Some(position) = self.recency.iter().position(|k| k == key) {
+                self.recency.remove(position);
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod lru_cache_tests {
+    use super::*;
+
+    #[test]
+    fn get_returns_none_for_an_absent_key() {
+        let mut cache: LruCache<u32, u32> = LruCache::new(2);
+        assert_eq!(None, cache.get(&1));
+    }
+
+    #[test]
+    fn put_then_get_round_trips() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, "one");
+        assert_eq!(Some(&"one"), cache.get(&1));
+    }
+
+    #[test]
+    fn put_evicts_the_least_recently_touched_key_once_full() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, "one");
+        cache.put(2, "two");
+        cache.put(3, "three");
+
+        assert_eq!(None, cache.get(&1));
+        assert_eq!(Some(&"two"), cache.get(&2));
+        assert_eq!(Some(&"three"), cache.get(&3));
+    }
+
+    #[test]
+    fn getting_a_key_protects_it_from_the_next_eviction() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, "one");
+        cache.put(2, "two");
+        cache.get(&1);
+        cache.put(3, "three");
+
+        assert_eq!(Some(&"one"), cache.get(&1));
+        assert_eq!(None, cache.get(&2));
+        assert_eq!(Some(&"three"), cache.get(&3));
+    }
+
+    #[test]
+    fn invalidate_removes_a_key_without_disturbing_others() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, "one");
+        cache.put(2, "two");
+        cache.invalidate(&1);
+
+        assert_eq!(None, cache.get(&1));
+        assert_eq!(Some(&"two"), cache.get(&2));
+        assert_eq!(1, cache.len());
+    }
+
+    #[test]
+    fn clear_empties_the_cache() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, "one");
+        cache.put(2, "two");
+        cache.clear();
+
+        assert!(cache.is_empty());
+        assert_eq!(None, cache.get(&1));
+    }
+
+    #[test]
+    fn zero_capacity_cache_never_retains_anything() {
+        let mut cache = LruCache::new(0);
+        cache.put(1, "one");
+        assert_eq!(None, cache.get(&1));
+    }
+}
@@ -0,0 +1,153 @@
+//! Commit to an ordered collection of small [`MerkleTree`]s under one super-root, so that a caller
+//! who wants "one commitment per table, all summarized under one top-level commitment" (a pattern
+//! otherwise hand-assembled by taking the trees' roots and building yet another `MerkleTree` over
+//! them) gets it as a single type, together with a two-level opening: one authentication structure
+//! for a leaf inside its own tree, and one for that tree's root inside the forest of roots.
+
+use crate::shared_math::digest::Digest;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
+use crate::util_types::merkle_tree::CpuParallel;
+use crate::util_types::merkle_tree::MerkleTree;
+use crate::util_types::merkle_tree::MerkleTreeInclusionProof;
+use crate::util_types::merkle_tree_maker::MerkleTreeMaker;
+
+/// An ordered collection of [`MerkleTree`]s, itself committed to by a `MerkleTree` over their
+/// roots. The number of trees, like the number of leaves in any individual tree, must be a power
+/// of two.
+pub struct MerkleForest<H>
+where
+    H: AlgebraicHasher,
+{
+    trees: Vec<MerkleTree<H>>,
+    root_tree: MerkleTree<H>,
+}
+
+impl<H> MerkleForest<H>
+where
+    H: AlgebraicHasher,
+{
+    pub fn new(trees: Vec<MerkleTree<H>>) -> Self {
+        let roots: Vec<Digest> = trees.iter().map(MerkleTree::get_root).collect();
+        let root_tree: MerkleTree<H> = CpuParallel::from_digests(&roots);
+        Self { trees, root_tree }
+    }
+
+    /// Build a forest directly from each tree's leaves.
+    pub fn from_leaf_groups(leaf_groups: &[Vec<Digest>]) -> Self {
+        let trees = leaf_groups
+            .iter()
+            .map(|leaves| CpuParallel::from_digests(leaves))
+            .collect();
+        Self::new(trees)
+    }
+
+    /// The single digest committing to every tree in the forest.
+    pub fn get_super_root(&self) -> Digest {
+        self.root_tree.get_root()
+    }
+
+    pub fn get_tree_count(&self) -> usize {
+        self.trees.len()
+    }
+
+    pub fn get_tree(&self, tree_index: usize) -> &MerkleTree<H> {
+        &self.trees[tree_index]
+    }
+
+    /// Produce a two-level opening for the leaf at `leaf_index` of the tree at `tree_index`: an
+    /// [`MerkleTreeInclusionProof`] of the leaf within its own tree, and one of that tree's root
+    /// within the forest's root tree.
+    pub fn prove(&self, tree_index: usize, leaf_index: usize) -> MerkleForestInclusionProof<H> {
+        let tree = &self.trees[tree_index];
+        let leaf_digest = tree.get_leaf_by_index(leaf_index);
+        let inner = MerkleTreeInclusionProof::new(
+            tree.get_height() as u32,
+            vec![(leaf_index as u64, leaf_digest)],
+            tree.get_authentication_structure(&[leaf_index]),
+        );
+        let outer = MerkleTreeInclusionProof::new(
+            self.root_tree.get_height() as u32,
+            vec![(tree_index as u64, tree.get_root())],
+            self.root_tree.get_authentication_structure(&[tree_index]),
+        );
+        MerkleForestInclusionProof { inner, outer }
+    }
+}
+
+/// A two-level opening produced by [`MerkleForest::prove`]: `inner` authenticates a leaf within
+/// its own tree against that tree's root, and `outer` authenticates that same root within the
+/// forest's root tree against the forest's super-root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleForestInclusionProof<H>
+where
+    H: AlgebraicHasher,
+{
+    pub inner: MerkleTreeInclusionProof<H>,
+    pub outer: MerkleTreeInclusionProof<H>,
+}
+
+impl<H> MerkleForestInclusionProof<H>
+where
+    H: AlgebraicHasher,
+{
+    /// Verify this opening against `expected_super_root`: the outer proof must open to the exact
+    /// tree root the inner proof is checked against, and both levels must verify.
+    pub fn verify(&self, expected_super_root: Digest) -> bool {
+        if self.inner.indexed_leafs.len() != 1 || self.outer.indexed_leafs.len() != 1 {
+            return false;
+        }
+        let claimed_tree_root = self.outer.indexed_leafs[0].1;
+        self.inner.verify(claimed_tree_root) && self.outer.verify(expected_super_root)
+    }
+}
+
+#[cfg(test)]
+mod merkle_forest_tests {
+    use crate::shared_math::other::random_elements;
+    use crate::shared_math::tip5::Tip5;
+    use crate::test_shared::corrupt_digest;
+
+    use super::*;
+
+    #[test]
+    fn honest_two_level_opening_verifies() {
+        type H = Tip5;
+
+        let leaf_groups: Vec<Vec<Digest>> = (0..4).map(|_| random_elements(8)).collect();
+        let forest = MerkleForest::<H>::from_leaf_groups(&leaf_groups);
+
+        for tree_index in 0..forest.get_tree_count() {
+            for leaf_index in 0..leaf_groups[tree_index].len() {
+                let proof = forest.prove(tree_index, leaf_index);
+                assert!(proof.verify(forest.get_super_root()));
+            }
+        }
+    }
+
+    #[test]
+    fn tampered_leaf_fails_verification() {
+        type H = Tip5;
+
+        let leaf_groups: Vec<Vec<Digest>> = (0..4).map(|_| random_elements(8)).collect();
+        let forest = MerkleForest::<H>::from_leaf_groups(&leaf_groups);
+
+        let mut proof = forest.prove(2, 3);
+        let (index, digest) = proof.inner.indexed_leafs[0];
+        proof.inner.indexed_leafs[0] = (index, corrupt_digest(&digest));
+
+        assert!(!proof.verify(forest.get_super_root()));
+    }
+
+    #[test]
+    fn wrong_super_root_fails_verification() {
+        type H = Tip5;
+
+        let leaf_groups: Vec<Vec<Digest>> = (0..4).map(|_| random_elements(8)).collect();
+        let forest = MerkleForest::<H>::from_leaf_groups(&leaf_groups);
+
+        let proof = forest.prove(1, 5);
+        let wrong_super_root = corrupt_digest(&forest.get_super_root());
+
+        assert!(!proof.verify(wrong_super_root));
+    }
+}
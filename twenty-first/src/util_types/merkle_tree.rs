@@ -3,24 +3,173 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use std::ops::Deref;
+use std::ops::DerefMut;
 
 use anyhow::anyhow;
 use anyhow::bail;
 use anyhow::Result;
+use get_size::GetSize;
 use itertools::Itertools;
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+use serde::Deserialize;
+use serde::Serialize;
 
+use crate::shared_math::bfield_codec::BFieldCodec;
 use crate::shared_math::digest::Digest;
 use crate::shared_math::other::{is_power_of_two, log_2_floor};
 use crate::util_types::algebraic_hasher::AlgebraicHasher;
+use crate::util_types::compact_authentication_path::CompactAuthenticationPath;
 use crate::util_types::merkle_tree_maker::MerkleTreeMaker;
 
+/// A de-duplicated authentication structure, as produced by
+/// [`MerkleTree::get_authentication_structure`]. A thin, [`BFieldCodec`]-able newtype around
+/// `Vec<Digest>` so that authentication structures can be embedded directly in a proof or stored
+/// on disk, instead of requiring an ad-hoc `bincode` wrapper around a bare `Vec`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, GetSize, BFieldCodec)]
+pub struct AuthenticationStructure(Vec<Digest>);
+
+impl Deref for AuthenticationStructure {
+    type Target = Vec<Digest>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for AuthenticationStructure {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Vec<Digest>> for AuthenticationStructure {
+    fn from(digests: Vec<Digest>) -> Self {
+        Self(digests)
+    }
+}
+
+impl From<AuthenticationStructure> for Vec<Digest> {
+    fn from(structure: AuthenticationStructure) -> Self {
+        structure.0
+    }
+}
+
+impl FromIterator<Digest> for AuthenticationStructure {
+    fn from_iter<T: IntoIterator<Item = Digest>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for AuthenticationStructure {
+    type Item = Digest;
+    type IntoIter = std::vec::IntoIter<Digest>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// A serializable snapshot of a partial Merkle tree — the nodes an
+/// [`AuthenticationStructure`] and a set of opened leaves determine, keyed by node index — as
+/// computed by [`MerkleTree::partial_tree`]. Stored as a sorted `Vec` of `(node index, digest)`
+/// pairs rather than a `HashMap`, both because `HashMap` has no [`BFieldCodec`] impl and because a
+/// `Vec` gives a canonical, order-independent encoding.
+///
+/// [`MerkleTree::graft_partial_trees`] merges in the partial tree for leaves revealed in a later
+/// round, so a stateless verifier can accumulate an opening across several rounds without keeping
+/// every round's raw authentication structure around.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, GetSize, BFieldCodec)]
+pub struct PartialMerkleTree(Vec<(u64, Digest)>);
+
+impl From<HashMap<usize, Digest>> for PartialMerkleTree {
+    fn from(partial_tree: HashMap<usize, Digest>) -> Self {
+        let mut entries = partial_tree
+            .into_iter()
+            .map(|(node_index, digest)| (node_index as u64, digest))
+            .collect_vec();
+        entries.sort_unstable_by_key(|(node_index, _)| *node_index);
+        Self(entries)
+    }
+}
+
+impl From<PartialMerkleTree> for HashMap<usize, Digest> {
+    fn from(partial_tree: PartialMerkleTree) -> Self {
+        partial_tree
+            .0
+            .into_iter()
+            .map(|(node_index, digest)| (node_index as usize, digest))
+            .collect()
+    }
+}
+
+/// A self-contained proof that a set of leaves, at their claimed indices, are members of the
+/// Merkle tree with the claimed root — a “Merkle multiproof.” Bundles exactly the data
+/// [`MerkleTree::verify_authentication_structure`] needs, so a whole opening can be encoded with
+/// [`BFieldCodec`] and shipped as a single unit instead of as several loose vectors.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, GetSize, BFieldCodec)]
+pub struct MerkleTreeInclusionProof<H>
+where
+    H: AlgebraicHasher,
+{
+    pub tree_height: u32,
+    pub indexed_leafs: Vec<(u64, Digest)>,
+    pub authentication_structure: AuthenticationStructure,
+    #[bfield_codec(ignore)]
+    _hasher: PhantomData<H>,
+}
+
+impl<H> MerkleTreeInclusionProof<H>
+where
+    H: AlgebraicHasher,
+{
+    pub fn new(
+        tree_height: u32,
+        indexed_leafs: Vec<(u64, Digest)>,
+        authentication_structure: AuthenticationStructure,
+    ) -> Self {
+        Self {
+            tree_height,
+            indexed_leafs,
+            authentication_structure,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Verify this proof against `expected_root`. See
+    /// [`MerkleTree::verify_authentication_structure`].
+    pub fn verify(&self, expected_root: Digest) -> bool {
+        let leaf_indices = self
+            .indexed_leafs
+            .iter()
+            .map(|&(index, _)| index as usize)
+            .collect_vec();
+        let leaf_digests = self
+            .indexed_leafs
+            .iter()
+            .map(|&(_, digest)| digest)
+            .collect_vec();
+        MerkleTree::<H>::verify_authentication_structure(
+            expected_root,
+            self.tree_height as usize,
+            &leaf_indices,
+            &leaf_digests,
+            &self.authentication_structure,
+        )
+    }
+}
+
 // Chosen from a very small number of benchmark runs, optimized for a slow
 // hash function (the original Rescue Prime implementation). It should probably
 // be a higher number than 16 when using a faster hash function.
 const PARALLELLIZATION_THRESHOLD: usize = 16;
 
-#[derive(Debug, Clone)]
+/// Nodes are stored in a single, contiguous, 1-indexed `Vec<Digest>` in breadth-first (heap) order:
+/// the root is `nodes[1]`, and a node's children live at `nodes[2 * i]` and `nodes[2 * i + 1]`.
+/// This flat layout, rather than a tree of pointers or a `Vec` per level, is what makes the whole
+/// tree `Serialize`/`Deserialize` as one contiguous digest array and keeps sibling/parent lookups
+/// to plain index arithmetic with good cache locality.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MerkleTree<H>
 where
     H: AlgebraicHasher,
@@ -39,15 +188,17 @@ where
     H: AlgebraicHasher,
 {
     /// Given a list of leaf indices, return the indices of exactly those nodes that are needed to
-    /// prove (or verify) that the indicated leaves are in the Merkle tree.
+    /// prove (or verify) that the indicated leaves are in the Merkle tree, up to the layer of
+    /// `num_cap_nodes` ancestors closest to the root (`num_cap_nodes == 1` means all the way up
+    /// to the root itself; see [`cap`][Self::cap] for larger caps).
     // This function is not defined as a method (taking self as argument) since it's
     // needed by the verifier who does not have access to the Merkle tree.
     fn indices_of_nodes_in_authentication_structure(
         num_nodes: usize,
+        num_cap_nodes: usize,
         leaf_indices: &[usize],
     ) -> Vec<usize> {
         let num_leaves = num_nodes / 2;
-        let root_index = 1;
 
         let all_indices_are_valid = leaf_indices
             .iter()
@@ -55,19 +206,20 @@ where
         assert!(all_indices_are_valid, "All leaf indices must be valid.");
 
         // The set of indices of nodes that need to be included in the authentications structure.
-        // In principle, every node of every authentication path is needed. The root is never
-        // needed. Hence, it is not considered in the computation below.
+        // In principle, every node of every authentication path is needed. A node in the cap is
+        // never needed, since it is given directly. Hence, cap nodes are not considered in the
+        // computation below.
         let mut node_is_needed = HashSet::new();
 
         // The set of indices of nodes that can be computed from other nodes in the authentication
         // structure or the leafs that are explicitly supplied during verification.
-        // Every node on the direct path from the leaf to the root can be computed by the very
+        // Every node on the direct path from the leaf to the cap can be computed by the very
         // nature of “authentication path”.
         let mut node_can_be_computed = HashSet::new();
 
         for leaf_index in leaf_indices {
             let mut node_index = leaf_index + num_leaves;
-            while node_index > root_index {
+            while node_index >= 2 * num_cap_nodes {
                 let sibling_index = node_index ^ 1;
                 node_can_be_computed.insert(node_index);
                 node_is_needed.insert(sibling_index);
@@ -116,9 +268,41 @@ where
     /// This is the other part of the de-duplication.
     ///
     /// [verify]: Self::verify_authentication_structure
-    pub fn get_authentication_structure(&self, leaf_indices: &[usize]) -> Vec<Digest> {
+    pub fn get_authentication_structure(&self, leaf_indices: &[usize]) -> AuthenticationStructure {
+        let num_nodes = self.nodes.len();
+        Self::indices_of_nodes_in_authentication_structure(num_nodes, 1, leaf_indices)
+            .into_iter()
+            .map(|idx| self.nodes[idx])
+            .collect()
+    }
+
+    /// The top `2^cap_height` nodes of the tree, in left-to-right order. A cap of height `0` is
+    /// just the root; larger cap heights trade a bigger commitment (`2^cap_height` digests instead
+    /// of one) for shorter authentication structures, since
+    /// [`get_authentication_structure_relative_to_cap`][Self::get_authentication_structure_relative_to_cap]
+    /// only needs to authenticate up to the cap rather than all the way to the root.
+    pub fn cap(&self, cap_height: usize) -> Vec<Digest> {
+        let num_cap_nodes = 1 << cap_height;
+        assert!(
+            num_cap_nodes <= self.get_leaf_count(),
+            "Cap height {cap_height} is too large for a tree with {} leaves.",
+            self.get_leaf_count()
+        );
+        self.nodes[num_cap_nodes..2 * num_cap_nodes].to_vec()
+    }
+
+    /// Like [`get_authentication_structure`][Self::get_authentication_structure], but stops at the
+    /// tree's [`cap`][Self::cap] of the indicated height instead of continuing all the way to the
+    /// root. Verify with
+    /// [`verify_authentication_structure_against_cap`][Self::verify_authentication_structure_against_cap].
+    pub fn get_authentication_structure_relative_to_cap(
+        &self,
+        cap_height: usize,
+        leaf_indices: &[usize],
+    ) -> AuthenticationStructure {
         let num_nodes = self.nodes.len();
-        Self::indices_of_nodes_in_authentication_structure(num_nodes, leaf_indices)
+        let num_cap_nodes = 1 << cap_height;
+        Self::indices_of_nodes_in_authentication_structure(num_nodes, num_cap_nodes, leaf_indices)
             .into_iter()
             .map(|idx| self.nodes[idx])
             .collect()
@@ -131,7 +315,7 @@ where
         tree_height: usize,
         leaf_indices: &[usize],
         leaf_digests: &[Digest],
-        authentication_structure: &[Digest],
+        authentication_structure: &AuthenticationStructure,
     ) -> bool {
         if leaf_indices.is_empty() && leaf_digests.is_empty() && authentication_structure.is_empty()
         {
@@ -139,6 +323,7 @@ where
         }
         let mut partial_tree = match Self::partial_tree_from_authentication_structure(
             tree_height,
+            1,
             leaf_indices,
             leaf_digests,
             authentication_structure,
@@ -146,20 +331,63 @@ where
             Ok(tree) => tree,
             Err(_) => return false,
         };
-        if Self::fill_partial_tree(&mut partial_tree, tree_height, leaf_indices).is_err() {
+        if Self::fill_partial_tree(&mut partial_tree, tree_height, 0, leaf_indices).is_err() {
             return false;
         }
         let computed_root = partial_tree[&1];
         computed_root == expected_root
     }
 
+    /// Verify a list of indicated digests and corresponding authentication structure against a
+    /// tree [`cap`][Self::cap] of the indicated height, instead of against a single root. See also
+    /// [`get_authentication_structure_relative_to_cap`][Self::get_authentication_structure_relative_to_cap].
+    pub fn verify_authentication_structure_against_cap(
+        cap: &[Digest],
+        cap_height: usize,
+        tree_height: usize,
+        leaf_indices: &[usize],
+        leaf_digests: &[Digest],
+        authentication_structure: &AuthenticationStructure,
+    ) -> bool {
+        let num_cap_nodes = 1 << cap_height;
+        if cap.len() != num_cap_nodes {
+            return false;
+        }
+        if leaf_indices.is_empty() && leaf_digests.is_empty() && authentication_structure.is_empty()
+        {
+            return true;
+        }
+        let mut partial_tree = match Self::partial_tree_from_authentication_structure(
+            tree_height,
+            num_cap_nodes,
+            leaf_indices,
+            leaf_digests,
+            authentication_structure,
+        ) {
+            Ok(tree) => tree,
+            Err(_) => return false,
+        };
+        if Self::fill_partial_tree(&mut partial_tree, tree_height, cap_height, leaf_indices)
+            .is_err()
+        {
+            return false;
+        }
+
+        let num_leaves = 1 << tree_height;
+        leaf_indices.iter().all(|&leaf_index| {
+            let cap_index = (leaf_index + num_leaves) >> (tree_height - cap_height);
+            partial_tree[&cap_index] == cap[cap_index - num_cap_nodes]
+        })
+    }
+
     /// Given a list of leaf indices and corresponding digests as well as an authentication
-    /// structure for a tree of indicated height, build a partial Merkle tree.
+    /// structure for a tree of indicated height, build a partial Merkle tree containing everything
+    /// up to (but not including) the layer of `num_cap_nodes` ancestors closest to the root.
     ///
     /// Continuing the example from
     /// [`get_authentication_structure`][Self::get_authentication_structure],
     /// the partial tree for leaves 0 and 2, _i.e._, nodes 8 and 10 respectively,
-    /// with nodes [11, 9, 3] from the authentication structure is:
+    /// with nodes [11, 9, 3] from the authentication structure (`num_cap_nodes == 1`) is:
     ///
     /// ```markdown
     ///         ──── _ ────
@@ -171,11 +399,109 @@ where
     ///   ╱ ╲    ╱ ╲
     ///  8   9  10 11
     /// ```
+    /// Build a serializable [`PartialMerkleTree`] from an authentication structure, without
+    /// immediately verifying it against a root or cap. Useful for stateless verification workflows
+    /// that want to persist the partial tree as-is and only fill in and verify it once further
+    /// leaves have been [grafted in][Self::graft_partial_trees].
+    pub fn partial_tree(
+        tree_height: usize,
+        leaf_indices: &[usize],
+        leaf_digests: &[Digest],
+        authentication_structure: &AuthenticationStructure,
+    ) -> Result<PartialMerkleTree> {
+        Self::partial_tree_from_authentication_structure(
+            tree_height,
+            1,
+            leaf_indices,
+            leaf_digests,
+            authentication_structure,
+        )
+        .map(PartialMerkleTree::from)
+    }
+
+    /// Graft `newly_revealed` — a partial tree opening `newly_revealed_leaf_indices` — onto
+    /// `existing`, an already-held partial tree opening `existing_leaf_indices`, producing the
+    /// minimal partial tree for the union of both leaf sets.
+    ///
+    /// This lets a stateless verifier accumulate an authentication structure across several rounds
+    /// of newly revealed leaves, instead of keeping every round's raw authentication structure
+    /// around and re-deriving the union's minimal partial tree from scratch. Fails if the two
+    /// partial trees disagree on the digest of some node index they both contain, or if the union
+    /// is missing information needed to authenticate every leaf in the combined leaf set.
+    pub fn graft_partial_trees(
+        tree_height: usize,
+        existing_leaf_indices: &[usize],
+        existing: &PartialMerkleTree,
+        newly_revealed_leaf_indices: &[usize],
+        newly_revealed: &PartialMerkleTree,
+    ) -> Result<PartialMerkleTree> {
+        let num_leaves = 1 << tree_height;
+
+        let mut known: HashMap<usize, Digest> = HashMap::new();
+        for &(node_index, digest) in existing.0.iter().chain(newly_revealed.0.iter()) {
+            let node_index = node_index as usize;
+            match known.entry(node_index) {
+                Occupied(entry) if *entry.get() != digest => {
+                    bail!("Conflicting digest for node {node_index} while grafting partial trees.")
+                }
+                Occupied(_) => (),
+                Vacant(entry) => {
+                    entry.insert(digest);
+                }
+            }
+        }
+
+        // Derive every node computable from what is known so far, bottom-up, tolerating gaps:
+        // unlike `fill_partial_tree`, a node whose children are not both known is simply skipped,
+        // since combining two partial views can leave some internal nodes still out of reach.
+        for node_index in (1..num_leaves).rev() {
+            if known.contains_key(&node_index) {
+                continue;
+            }
+            let (left, right) = (node_index * 2, node_index * 2 + 1);
+            if let (Some(&l), Some(&r)) = (known.get(&left), known.get(&right)) {
+                known.insert(node_index, H::hash_pair(&l, &r));
+            }
+        }
+
+        let mut combined_leaf_indices = existing_leaf_indices.to_vec();
+        combined_leaf_indices.extend_from_slice(newly_revealed_leaf_indices);
+        combined_leaf_indices.sort_unstable();
+        combined_leaf_indices.dedup();
+
+        let num_nodes = num_leaves * 2;
+        let needed_indices = Self::indices_of_nodes_in_authentication_structure(
+            num_nodes,
+            1,
+            &combined_leaf_indices,
+        );
+
+        let mut result = Vec::with_capacity(needed_indices.len() + combined_leaf_indices.len());
+        for node_index in needed_indices {
+            let &digest = known.get(&node_index).ok_or_else(|| {
+                anyhow!("Cannot graft: node {node_index} is not derivable from the supplied partial trees.")
+            })?;
+            result.push((node_index as u64, digest));
+        }
+        for &leaf_index in &combined_leaf_indices {
+            let node_index = leaf_index + num_leaves;
+            let &digest = known.get(&node_index).ok_or_else(|| {
+                anyhow!(
+                    "Cannot graft: leaf {leaf_index} is not present in the supplied partial trees."
+                )
+            })?;
+            result.push((node_index as u64, digest));
+        }
+        result.sort_unstable_by_key(|&(i, _)| i);
+        Ok(PartialMerkleTree(result))
+    }
+
     fn partial_tree_from_authentication_structure(
         tree_height: usize,
+        num_cap_nodes: usize,
         leaf_indices: &[usize],
         leaf_digests: &[Digest],
-        authentication_structure: &[Digest],
+        authentication_structure: &AuthenticationStructure,
     ) -> Result<HashMap<usize, Digest>> {
         let num_leaves = 1 << tree_height;
         let num_nodes = num_leaves * 2;
@@ -191,7 +517,11 @@ where
         }
 
         let indices_of_nodes_in_authentication_structure =
-            Self::indices_of_nodes_in_authentication_structure(num_nodes, leaf_indices);
+            Self::indices_of_nodes_in_authentication_structure(
+                num_nodes,
+                num_cap_nodes,
+                leaf_indices,
+            );
         if authentication_structure.len() != indices_of_nodes_in_authentication_structure.len() {
             bail!("The length of the supplied authentication must match the expected length.");
         }
@@ -212,18 +542,22 @@ where
         Ok(partial_merkle_tree)
     }
 
-    /// Compute all computable digests of the partial Merkle tree, modifying the given partial tree.
-    /// Returns an error if the given tree is either
-    /// - incomplete, _i.e._, does not contain all the nodes required to compute the root, or
+    /// Compute all computable digests of the partial Merkle tree, modifying the given partial tree,
+    /// stopping at the layer of `2^cap_height` ancestors closest to the root (`cap_height == 0`
+    /// computes all the way up to the root). Returns an error if the given tree is either
+    /// - incomplete, _i.e._, does not contain all the nodes required to reach the cap layer, or
     /// - not minimal, _i.e._, if it contains nodes that can be computed from other nodes.
     ///
-    /// On success, the given partial tree is guaranteed to contain the root digest at index 1.
+    /// On success, the given partial tree is guaranteed to contain every node of the cap layer that
+    /// is an ancestor of one of `leaf_indices` (index `1`, i.e. the root, when `cap_height == 0`).
     fn fill_partial_tree(
         partial_tree: &mut HashMap<usize, Digest>,
         tree_height: usize,
+        cap_height: usize,
         leaf_indices: &[usize],
     ) -> Result<()> {
         let num_leaves = 1 << tree_height;
+        let levels_to_climb = tree_height - cap_height;
 
         // Deduplicate parent node indices to avoid hashing the same nodes twice,
         // which happens when two leaves are siblings.
@@ -234,8 +568,8 @@ where
         parent_node_indices.sort_unstable();
         parent_node_indices.dedup();
 
-        // hash the partial tree from the bottom up
-        for _ in 0..tree_height {
+        // hash the partial tree from the bottom up, until the cap layer is reached
+        for _ in 0..levels_to_climb {
             for &parent_node_index in parent_node_indices.iter() {
                 let left_node_index = parent_node_index * 2;
                 let right_node_index = left_node_index ^ 1;
@@ -272,8 +606,12 @@ where
             parent_node_indices.dedup();
         }
 
-        if !partial_tree.contains_key(&1) {
-            bail!("Could not compute the root. Maybe no leaf indices were supplied?");
+        let reached_cap = leaf_indices
+            .iter()
+            .map(|&leaf_index| (leaf_index + num_leaves) >> levels_to_climb)
+            .all(|cap_index| partial_tree.contains_key(&cap_index));
+        if !reached_cap {
+            bail!("Could not compute the cap. Maybe no leaf indices were supplied?");
         }
 
         Ok(())
@@ -307,18 +645,42 @@ where
         tree_height: usize,
         leaf_indices: &[usize],
         leaf_digests: &[Digest],
-        authentication_structure: &[Digest],
+        authentication_structure: &AuthenticationStructure,
     ) -> Result<Vec<Vec<Digest>>> {
         let mut partial_tree = Self::partial_tree_from_authentication_structure(
             tree_height,
+            1,
             leaf_indices,
             leaf_digests,
             authentication_structure,
         )?;
-        Self::fill_partial_tree(&mut partial_tree, tree_height, leaf_indices)?;
+        Self::fill_partial_tree(&mut partial_tree, tree_height, 0, leaf_indices)?;
         Self::authentication_paths_from_partial_tree(&partial_tree, tree_height, leaf_indices)
     }
 
+    /// Same as [`Self::authentication_paths_from_authentication_structure`], but each path is
+    /// packed into a [`CompactAuthenticationPath`], bundling the left/right decisions with the
+    /// digests instead of leaving them to be re-derived from `leaf_indices` and `tree_height` at
+    /// verification time.
+    pub fn compact_authentication_paths_from_authentication_structure(
+        tree_height: usize,
+        leaf_indices: &[usize],
+        leaf_digests: &[Digest],
+        authentication_structure: &AuthenticationStructure,
+    ) -> Result<Vec<CompactAuthenticationPath>> {
+        let paths = Self::authentication_paths_from_authentication_structure(
+            tree_height,
+            leaf_indices,
+            leaf_digests,
+            authentication_structure,
+        )?;
+        Ok(leaf_indices
+            .iter()
+            .zip_eq(paths)
+            .map(|(&leaf_index, path)| CompactAuthenticationPath::new(leaf_index as u64, path))
+            .collect())
+    }
+
     /// Given a partial Merkle tree, collect the authentication paths for the indicated leaves.
     fn authentication_paths_from_partial_tree(
         partial_tree: &HashMap<usize, Digest>,
@@ -402,11 +764,112 @@ where
         }
         result
     }
+
+    /// Read a node of the 1-indexed flat node array by its raw index. Exposed crate-internally so
+    /// that variants like [`MutableMerkleTree`][crate::util_types::mutable_merkle_tree::MutableMerkleTree]
+    /// can address individual nodes without duplicating this tree's indexing scheme.
+    pub(crate) fn node_at(&self, node_index: usize) -> Digest {
+        self.nodes[node_index]
+    }
+
+    pub(crate) fn set_node(&mut self, node_index: usize, digest: Digest) {
+        self.nodes[node_index] = digest;
+    }
 }
 
+/// Verify a set of indexed leaves against a Merkle root, without building or holding a
+/// [`MerkleTree`]. A free function wrapping
+/// [`MerkleTree::verify_authentication_structure`][MerkleTree::verify_authentication_structure]
+/// for verifier-side code that only ever has a root, some opened leaves, and an authentication
+/// structure — never a prover's tree — and so has no `MerkleTree<H>` value to call a method on in
+/// the first place. Costs no hashing of leaves outside `leaf_indices` and no allocation
+/// proportional to the tree's total leaf count; only the authentication structure and the opened
+/// leaves are touched.
+pub fn verify_authentication_structure<H: AlgebraicHasher>(
+    expected_root: Digest,
+    tree_height: usize,
+    leaf_indices: &[usize],
+    leaf_digests: &[Digest],
+    authentication_structure: &AuthenticationStructure,
+) -> bool {
+    MerkleTree::<H>::verify_authentication_structure(
+        expected_root,
+        tree_height,
+        leaf_indices,
+        leaf_digests,
+        authentication_structure,
+    )
+}
+
+/// Verify a single leaf's authentication path against a Merkle root by folding the path's sibling
+/// digests one at a time from an iterator, using constant memory: only the current node index and
+/// a running accumulator digest are held at any point, never the whole path collected into a `Vec`
+/// or a partial tree built out of a `HashMap`. Suited to verifiers that cannot afford an allocation
+/// proportional to the tree's height, e.g. an embedded or `no_std` target streaming a proof off a
+/// byte stream sibling by sibling.
+pub fn verify_leaf_inclusion_streaming<H: AlgebraicHasher>(
+    expected_root: Digest,
+    tree_height: usize,
+    leaf_index: usize,
+    leaf_digest: Digest,
+    authentication_path: impl IntoIterator<Item = Digest>,
+) -> bool {
+    let mut node_index = leaf_index + (1 << tree_height);
+    let mut acc_hash = leaf_digest;
+    let mut path_length = 0;
+
+    for sibling in authentication_path {
+        if node_index <= 1 {
+            return false;
+        }
+        acc_hash = if node_index.is_multiple_of(2) {
+            H::hash_pair(&acc_hash, &sibling)
+        } else {
+            H::hash_pair(&sibling, &acc_hash)
+        };
+        node_index /= 2;
+        path_length += 1;
+    }
+
+    path_length == tree_height && node_index == 1 && acc_hash == expected_root
+}
+
+/// Failure modes for building a [`MerkleTree`], reported by [`CpuParallel::try_from_digests`]
+/// instead of the panic that [`MerkleTreeMaker::from_digests`] raises for the same conditions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MerkleTreeError {
+    EmptyInput,
+    LeafCountNotPowerOfTwo(usize),
+}
+
+impl std::fmt::Display for MerkleTreeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl std::error::Error for MerkleTreeError {}
+
 #[derive(Debug)]
 pub struct CpuParallel;
 
+impl CpuParallel {
+    /// Fallible alternative to [`MerkleTreeMaker::from_digests`]: reports why construction failed
+    /// as a [`MerkleTreeError`] instead of panicking, for callers that build trees from
+    /// externally-supplied leaf lists and want to report a precise cause rather than crash.
+    pub fn try_from_digests<H: AlgebraicHasher>(
+        digests: &[Digest],
+    ) -> Result<MerkleTree<H>, MerkleTreeError> {
+        if digests.is_empty() {
+            return Err(MerkleTreeError::EmptyInput);
+        }
+        if !is_power_of_two(digests.len()) {
+            return Err(MerkleTreeError::LeafCountNotPowerOfTwo(digests.len()));
+        }
+        Ok(<Self as MerkleTreeMaker<H>>::from_digests(digests))
+    }
+}
+
 impl<H: AlgebraicHasher> MerkleTreeMaker<H> for CpuParallel {
     /// Takes an array of digests and builds a MerkleTree over them.
     /// The digests are used copied over as the leaves of the tree.
@@ -858,6 +1321,7 @@ pub mod merkle_tree_test {
         // is node index 7. `x` is node index 2.
         let needed_nodes = MerkleTree::<Tip5>::indices_of_nodes_in_authentication_structure(
             tree_a.get_leaf_count() * 2,
+            1,
             &[leaf_index_a],
         );
         assert_eq!(vec![7, 2], needed_nodes);
@@ -1068,6 +1532,7 @@ pub mod merkle_tree_test {
 
         let partial_mt = MT::partial_tree_from_authentication_structure(
             merkle_tree.get_height(),
+            1,
             &opened_leaf_indices,
             &opened_leaves,
             &authentication_structure,
@@ -1112,7 +1577,7 @@ pub mod merkle_tree_test {
         partial_tree.insert(9, rand::random());
         partial_tree.insert(10, rand::random());
         partial_tree.insert(11, rand::random());
-        MT::fill_partial_tree(&mut partial_tree, tree_height, &[0, 2]).unwrap();
+        MT::fill_partial_tree(&mut partial_tree, tree_height, 0, &[0, 2]).unwrap();
     }
 
     #[test]
@@ -1135,7 +1600,7 @@ pub mod merkle_tree_test {
         partial_tree.insert(9, rand::random());
         partial_tree.insert(10, rand::random());
         partial_tree.insert(11, rand::random());
-        MT::fill_partial_tree(&mut partial_tree, tree_height, &[0, 2]).unwrap();
+        MT::fill_partial_tree(&mut partial_tree, tree_height, 0, &[0, 2]).unwrap();
     }
 
     #[test]
@@ -1160,7 +1625,7 @@ pub mod merkle_tree_test {
         partial_tree.insert(9, rand::random());
         partial_tree.insert(10, rand::random());
         partial_tree.insert(11, rand::random());
-        MT::fill_partial_tree(&mut partial_tree, tree_height, &[0, 2]).unwrap();
+        MT::fill_partial_tree(&mut partial_tree, tree_height, 0, &[0, 2]).unwrap();
     }
 
     #[test]
@@ -1198,4 +1663,413 @@ pub mod merkle_tree_test {
         let expected_path_1 = vec![nodes[11], nodes[4], nodes[3]];
         assert_eq!(expected_path_1, authentication_paths[1]);
     }
+
+    #[test]
+    fn streaming_verification_agrees_with_batch_verification_for_every_leaf() {
+        type H = Tip5;
+        type M = CpuParallel;
+        type MT = MerkleTree<H>;
+
+        let tree_height = 4;
+        let num_leaves = 1 << tree_height;
+        let leaves: Vec<Digest> = random_elements(num_leaves);
+        let tree: MT = M::from_digests(&leaves);
+
+        for leaf_index in 0..num_leaves {
+            let auth_structure = tree.get_authentication_structure(&[leaf_index]);
+            let path = MT::authentication_paths_from_authentication_structure(
+                tree_height,
+                &[leaf_index],
+                &[leaves[leaf_index]],
+                &auth_structure,
+            )
+            .unwrap()
+            .remove(0);
+
+            assert!(verify_leaf_inclusion_streaming::<H>(
+                tree.get_root(),
+                tree_height,
+                leaf_index,
+                leaves[leaf_index],
+                path.clone(),
+            ));
+
+            // A wrong leaf digest, a wrong root, or a truncated path must all be rejected.
+            assert!(!verify_leaf_inclusion_streaming::<H>(
+                tree.get_root(),
+                tree_height,
+                leaf_index,
+                corrupt_digest(&leaves[leaf_index]),
+                path.clone(),
+            ));
+            assert!(!verify_leaf_inclusion_streaming::<H>(
+                corrupt_digest(&tree.get_root()),
+                tree_height,
+                leaf_index,
+                leaves[leaf_index],
+                path.clone(),
+            ));
+            assert!(!verify_leaf_inclusion_streaming::<H>(
+                tree.get_root(),
+                tree_height,
+                leaf_index,
+                leaves[leaf_index],
+                path[..path.len() - 1].to_vec(),
+            ));
+        }
+    }
+
+    #[test]
+    fn compact_authentication_paths_pack_the_same_digests_as_the_plain_paths() {
+        type H = Tip5;
+        type M = CpuParallel;
+        type MT = MerkleTree<H>;
+
+        let tree_height = 3;
+        let num_leaves = 1 << tree_height;
+        let leafs: Vec<Digest> = random_elements(num_leaves);
+        let merkle_tree: MT = M::from_digests(&leafs);
+
+        let opened_leaf_indices = [0, 2, 7];
+        let opened_leaves = opened_leaf_indices.iter().map(|&i| leafs[i]).collect_vec();
+        let authentication_structure =
+            merkle_tree.get_authentication_structure(&opened_leaf_indices);
+
+        let plain_paths = MT::authentication_paths_from_authentication_structure(
+            tree_height,
+            &opened_leaf_indices,
+            &opened_leaves,
+            &authentication_structure,
+        )
+        .unwrap();
+        let compact_paths = MT::compact_authentication_paths_from_authentication_structure(
+            tree_height,
+            &opened_leaf_indices,
+            &opened_leaves,
+            &authentication_structure,
+        )
+        .unwrap();
+
+        assert_eq!(plain_paths.len(), compact_paths.len());
+        for (plain_path, compact_path) in plain_paths.iter().zip_eq(compact_paths.iter()) {
+            assert_eq!(plain_path.as_slice(), compact_path.digests());
+        }
+
+        // Leaf 7 is the rightmost leaf, so every ancestor on its path is a right child, meaning
+        // every sibling digest on the way up is a left one.
+        let rightmost_leaf_path = &compact_paths[2];
+        for level in 0..rightmost_leaf_path.len() {
+            assert!(rightmost_leaf_path.is_left_sibling(level));
+        }
+    }
+
+    #[test]
+    fn authentication_structure_bfield_codec_round_trip() {
+        type H = Tip5;
+        type M = CpuParallel;
+        type MT = MerkleTree<H>;
+
+        let leaves: Vec<Digest> = random_elements(8);
+        let tree: MT = M::from_digests(&leaves);
+        let auth_structure = tree.get_authentication_structure(&[1, 4, 6]);
+
+        let encoded = auth_structure.encode();
+        let decoded = *AuthenticationStructure::decode(&encoded).unwrap();
+        assert_eq!(auth_structure, decoded);
+    }
+
+    #[test]
+    fn partial_merkle_tree_round_trips_through_hash_map() {
+        let mut partial_tree = HashMap::new();
+        partial_tree.insert(3usize, random_elements::<Digest>(1)[0]);
+        partial_tree.insert(1usize, random_elements::<Digest>(1)[0]);
+
+        let as_partial_merkle_tree: PartialMerkleTree = partial_tree.clone().into();
+        let round_tripped: HashMap<usize, Digest> = as_partial_merkle_tree.into();
+        assert_eq!(partial_tree, round_tripped);
+    }
+
+    #[test]
+    fn partial_merkle_tree_bfield_codec_round_trip() {
+        let mut partial_tree = HashMap::new();
+        partial_tree.insert(5usize, random_elements::<Digest>(1)[0]);
+        let as_partial_merkle_tree: PartialMerkleTree = partial_tree.into();
+
+        let encoded = as_partial_merkle_tree.encode();
+        let decoded = *PartialMerkleTree::decode(&encoded).unwrap();
+        assert_eq!(as_partial_merkle_tree, decoded);
+    }
+
+    #[test]
+    fn grafting_leaves_revealed_in_two_rounds_reproduces_the_one_shot_partial_tree() {
+        type H = Tip5;
+        type M = CpuParallel;
+        type MT = MerkleTree<H>;
+
+        let leaves: Vec<Digest> = random_elements(8);
+        let tree: MT = M::from_digests(&leaves);
+
+        let all_at_once_indices = [1usize, 4, 6];
+        let all_at_once_structure = tree.get_authentication_structure(&all_at_once_indices);
+        let all_at_once_digests = all_at_once_indices.iter().map(|&i| leaves[i]).collect_vec();
+        let all_at_once = MT::partial_tree(
+            3,
+            &all_at_once_indices,
+            &all_at_once_digests,
+            &all_at_once_structure,
+        )
+        .unwrap();
+
+        let first_round_indices = [1usize];
+        let first_round_structure = tree.get_authentication_structure(&first_round_indices);
+        let first_round_digests = vec![leaves[1]];
+        let first_round = MT::partial_tree(
+            3,
+            &first_round_indices,
+            &first_round_digests,
+            &first_round_structure,
+        )
+        .unwrap();
+
+        let second_round_indices = [4usize, 6];
+        let second_round_structure = tree.get_authentication_structure(&second_round_indices);
+        let second_round_digests = second_round_indices
+            .iter()
+            .map(|&i| leaves[i])
+            .collect_vec();
+        let second_round = MT::partial_tree(
+            3,
+            &second_round_indices,
+            &second_round_digests,
+            &second_round_structure,
+        )
+        .unwrap();
+
+        let grafted = MT::graft_partial_trees(
+            3,
+            &first_round_indices,
+            &first_round,
+            &second_round_indices,
+            &second_round,
+        )
+        .unwrap();
+        assert_eq!(all_at_once, grafted);
+    }
+
+    #[test]
+    fn grafting_conflicting_digests_for_the_same_node_fails() {
+        type H = Tip5;
+        type MT = MerkleTree<H>;
+
+        let mut a = HashMap::new();
+        a.insert(3usize, random_elements::<Digest>(1)[0]);
+        let a: PartialMerkleTree = a.into();
+
+        let mut b = HashMap::new();
+        b.insert(3usize, random_elements::<Digest>(1)[0]);
+        let b: PartialMerkleTree = b.into();
+
+        assert!(MT::graft_partial_trees(3, &[], &a, &[], &b).is_err());
+    }
+
+    #[test]
+    fn merkle_tree_inclusion_proof_verifies_and_round_trips() {
+        type H = Tip5;
+        type M = CpuParallel;
+        type MT = MerkleTree<H>;
+
+        let leaves: Vec<Digest> = random_elements(16);
+        let tree: MT = M::from_digests(&leaves);
+
+        let opened_indices = [2usize, 5, 9];
+        let indexed_leafs = opened_indices
+            .iter()
+            .map(|&i| (i as u64, leaves[i]))
+            .collect_vec();
+        let authentication_structure = tree.get_authentication_structure(&opened_indices);
+
+        let proof = MerkleTreeInclusionProof::<H>::new(4, indexed_leafs, authentication_structure);
+        assert!(proof.verify(tree.get_root()));
+        assert!(!proof.verify(corrupt_digest(&tree.get_root())));
+
+        let encoded = proof.encode();
+        let decoded = *MerkleTreeInclusionProof::<H>::decode(&encoded).unwrap();
+        assert!(decoded.verify(tree.get_root()));
+    }
+
+    #[test]
+    fn standalone_verify_authentication_structure_agrees_with_method() {
+        type H = Tip5;
+        type M = CpuParallel;
+        type MT = MerkleTree<H>;
+
+        let leaves: Vec<Digest> = random_elements(8);
+        let tree: MT = M::from_digests(&leaves);
+
+        let opened_indices = [0usize, 3];
+        let opened_leaves = opened_indices.iter().map(|&i| leaves[i]).collect_vec();
+        let authentication_structure = tree.get_authentication_structure(&opened_indices);
+
+        assert!(verify_authentication_structure::<H>(
+            tree.get_root(),
+            tree.get_height(),
+            &opened_indices,
+            &opened_leaves,
+            &authentication_structure,
+        ));
+
+        assert!(!verify_authentication_structure::<H>(
+            corrupt_digest(&tree.get_root()),
+            tree.get_height(),
+            &opened_indices,
+            &opened_leaves,
+            &authentication_structure,
+        ));
+    }
+
+    #[test]
+    fn cap_of_height_zero_is_the_root() {
+        type H = Tip5;
+        type M = CpuParallel;
+        type MT = MerkleTree<H>;
+
+        let leaves: Vec<Digest> = random_elements(8);
+        let tree: MT = M::from_digests(&leaves);
+
+        assert_eq!(vec![tree.get_root()], tree.cap(0));
+    }
+
+    #[test]
+    fn cap_has_two_to_the_cap_height_many_nodes() {
+        type H = Tip5;
+        type M = CpuParallel;
+        type MT = MerkleTree<H>;
+
+        let leaves: Vec<Digest> = random_elements(16);
+        let tree: MT = M::from_digests(&leaves);
+
+        assert_eq!(4, tree.cap(2).len());
+        assert_eq!(&tree.nodes[4..8], tree.cap(2));
+    }
+
+    #[test]
+    fn authentication_structure_relative_to_cap_verifies() {
+        type H = Tip5;
+        type M = CpuParallel;
+        type MT = MerkleTree<H>;
+
+        let leaves: Vec<Digest> = random_elements(32);
+        let tree: MT = M::from_digests(&leaves);
+
+        let cap_height = 3;
+        let cap = tree.cap(cap_height);
+
+        let opened_indices = [1usize, 7, 13, 22];
+        let opened_leaves = opened_indices.iter().map(|&i| leaves[i]).collect_vec();
+        let capped_structure =
+            tree.get_authentication_structure_relative_to_cap(cap_height, &opened_indices);
+
+        assert!(MT::verify_authentication_structure_against_cap(
+            &cap,
+            cap_height,
+            tree.get_height(),
+            &opened_indices,
+            &opened_leaves,
+            &capped_structure,
+        ));
+    }
+
+    #[test]
+    fn tampered_cap_fails_verification() {
+        type H = Tip5;
+        type M = CpuParallel;
+        type MT = MerkleTree<H>;
+
+        let leaves: Vec<Digest> = random_elements(16);
+        let tree: MT = M::from_digests(&leaves);
+
+        let cap_height = 2;
+        let mut cap = tree.cap(cap_height);
+        // Opened leaf 5 authenticates against cap[1] (cap_index = (5+16) >> (4-2) = 5, slot
+        // 5 - 4 = 1), so that's the slot that must be corrupted for this test to be meaningful.
+        cap[1] = corrupt_digest(&cap[1]);
+
+        let opened_indices = [5usize];
+        let opened_leaves = opened_indices.iter().map(|&i| leaves[i]).collect_vec();
+        let capped_structure =
+            tree.get_authentication_structure_relative_to_cap(cap_height, &opened_indices);
+
+        assert!(!MT::verify_authentication_structure_against_cap(
+            &cap,
+            cap_height,
+            tree.get_height(),
+            &opened_indices,
+            &opened_leaves,
+            &capped_structure,
+        ));
+    }
+
+    #[test]
+    fn cap_of_wrong_length_fails_verification() {
+        type H = Tip5;
+        type M = CpuParallel;
+        type MT = MerkleTree<H>;
+
+        let leaves: Vec<Digest> = random_elements(16);
+        let tree: MT = M::from_digests(&leaves);
+
+        let cap_height = 2;
+        let wrong_cap = tree.cap(cap_height + 1);
+
+        let opened_indices = [5usize];
+        let opened_leaves = opened_indices.iter().map(|&i| leaves[i]).collect_vec();
+        let capped_structure =
+            tree.get_authentication_structure_relative_to_cap(cap_height, &opened_indices);
+
+        assert!(!MT::verify_authentication_structure_against_cap(
+            &wrong_cap,
+            cap_height,
+            tree.get_height(),
+            &opened_indices,
+            &opened_leaves,
+            &capped_structure,
+        ));
+    }
+
+    #[test]
+    fn try_from_digests_reports_the_specific_construction_failure() {
+        type H = Tip5;
+
+        assert_eq!(
+            MerkleTreeError::EmptyInput,
+            CpuParallel::try_from_digests::<H>(&[]).unwrap_err()
+        );
+
+        let leaves: Vec<Digest> = random_elements(3);
+        assert_eq!(
+            MerkleTreeError::LeafCountNotPowerOfTwo(3),
+            CpuParallel::try_from_digests::<H>(&leaves).unwrap_err()
+        );
+
+        let leaves: Vec<Digest> = random_elements(4);
+        let tree = CpuParallel::try_from_digests::<H>(&leaves).unwrap();
+        let expected: MerkleTree<H> = CpuParallel::from_digests(&leaves);
+        assert_eq!(expected.get_root(), tree.get_root());
+    }
+
+    #[test]
+    fn whole_tree_serializes_and_deserializes_as_the_flat_node_array_it_is() {
+        type H = Tip5;
+        type M = CpuParallel;
+        type MT = MerkleTree<H>;
+
+        let leaves: Vec<Digest> = random_elements(16);
+        let tree: MT = M::from_digests(&leaves);
+
+        let serialized = bincode::serialize(&tree).unwrap();
+        let deserialized: MT = bincode::deserialize(&serialized).unwrap();
+
+        assert_eq!(tree.get_root(), deserialized.get_root());
+        assert_eq!(tree.get_all_leaves(), deserialized.get_all_leaves());
+    }
 }
@@ -0,0 +1,333 @@
+//! A memory-mapped [`StorageBackend`], for huge tables where every value serializes to the same
+//! number of bytes (e.g. an archival table of hundreds of millions of [`Digest`](crate::shared_math::digest::Digest)s).
+//!
+//! Unlike [`LevelDbBackend`](super::storage_backend::LevelDbBackend), there is no LSM tree, no
+//! write-amplifying compaction, and no serialization on the read/write path: a `get` or `put` is
+//! an `O(1)` slot lookup followed by a raw memory copy into or out of the mapped file. The
+//! trade-off is the fixed-width-value assumption this file is named for, plus a key-index that is
+//! rebuilt into memory by scanning the whole file once at [`MmapBackend::open`] time.
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::path::Path;
+
+use memmap2::MmapMut;
+
+use super::storage_backend::{BatchOperation, StorageBackend};
+
+/// Byte offset 0 in a slot flags whether the rest of the slot holds a live entry. Anything else
+/// in that byte is read back as `false`, so a freshly-`set_len`-grown file (all zero bytes) reads
+/// as entirely empty without needing to be explicitly initialized.
+const OCCUPIED: u8 = 1;
+const VACANT: u8 = 0;
+
+/// A [`StorageBackend`] over one memory-mapped file, laid out as fixed-width slots of
+/// `1 + max_key_width + value_width` bytes: an occupied flag, the key padded to
+/// `max_key_width` with trailing zero bytes, then the value. `put`ting a key longer than
+/// `max_key_width`, or a value whose length isn't exactly `value_width`, panics rather than
+/// silently truncating or misaligning every later read.
+pub struct MmapBackend {
+    file: File,
+    mmap: MmapMut,
+    max_key_width: usize,
+    value_width: usize,
+    /// Maps a key to its slot index. Rebuilt by a single scan of the file in [`Self::open`].
+    slot_of_key: HashMap<Vec<u8>, usize>,
+    /// Vacated slots, reused by later `put`s of a new key before the file is grown again.
+    free_slots: Vec<usize>,
+    slot_count: usize,
+}
+
+impl MmapBackend {
+    fn slot_width(max_key_width: usize, value_width: usize) -> usize {
+        1 + max_key_width + value_width
+    }
+
+    /// Open (creating if necessary) an [`MmapBackend`] at `path`, sized for at least
+    /// `initial_capacity` slots of keys up to `max_key_width` bytes and values of exactly
+    /// `value_width` bytes each.
+    ///
+    /// Reopening a file created with different `max_key_width`/`value_width` values than it was
+    /// created with reads garbage; those two numbers aren't themselves persisted in the file, so
+    /// it is the caller's responsibility to keep them consistent across restarts, the same way a
+    /// [`super::database_vector::DatabaseVector`] caller is responsible for its `T`.
+    pub fn open(
+        path: impl AsRef<Path>,
+        max_key_width: usize,
+        value_width: usize,
+        initial_capacity: usize,
+    ) -> std::io::Result<Self> {
+        let slot_width = Self::slot_width(max_key_width, value_width);
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+
+        let min_len = (initial_capacity.max(1) * slot_width) as u64;
+        if file.metadata()?.len() < min_len {
+            file.set_len(min_len)?;
+        }
+        let file_len = file.metadata()?.len() as usize;
+        let slot_count = file_len / slot_width;
+
+        let mmap = unsafe { MmapMut::map_mut(&file)? };
+
+        let mut backend = Self {
+            file,
+            mmap,
+            max_key_width,
+            value_width,
+            slot_of_key: HashMap::new(),
+            free_slots: Vec::new(),
+            slot_count,
+        };
+        backend.reindex();
+        Ok(backend)
+    }
+
+    /// Rebuild `slot_of_key`/`free_slots` by scanning every slot once. Called once, from
+    /// [`Self::open`].
+    fn reindex(&mut self) {
+        let slot_width = Self::slot_width(self.max_key_width, self.value_width);
+        for slot in 0..self.slot_count {
+            let offset = slot * slot_width;
+            if self.mmap[offset] != OCCUPIED {
+                self.free_slots.push(slot);
+                continue;
+            }
+            let key_start = offset + 1;
+            let key = trim_padding(&self.mmap[key_start..key_start + self.max_key_width]);
+            self.slot_of_key.insert(key, slot);
+        }
+    }
+
+    fn slot_width_of(&self) -> usize {
+        Self::slot_width(self.max_key_width, self.value_width)
+    }
+
+    fn value_range(&self, slot: usize) -> std::ops::Range<usize> {
+        let offset = slot * self.slot_width_of();
+        let value_start = offset + 1 + self.max_key_width;
+        value_start..value_start + self.value_width
+    }
+
+    /// Double the file's slot capacity, remapping the file in place. `put` calls this whenever
+    /// no free slot is available.
+    fn grow(&mut self) {
+        let slot_width = self.slot_width_of();
+        let new_slot_count = (self.slot_count * 2).max(1);
+        self.mmap
+            .flush()
+            .expect("Flushing before growing the mmap backend must succeed");
+        self.file
+            .set_len((new_slot_count * slot_width) as u64)
+            .expect("Growing the mmap backend's file must succeed");
+        self.mmap = unsafe {
+            MmapMut::map_mut(&self.file)
+                .expect("Remapping the grown mmap backend file must succeed")
+        };
+        for slot in self.slot_count..new_slot_count {
+            self.free_slots.push(slot);
+        }
+        self.slot_count = new_slot_count;
+    }
+
+    fn allocate_slot(&mut self) -> usize {
+        if self.free_slots.is_empty() {
+            self.grow();
+        }
+        self.free_slots.pop().expect("Just grew if empty")
+    }
+}
+
+fn trim_padding(padded: &[u8]) -> Vec<u8> {
+    let trimmed_len = padded.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    padded[..trimmed_len].to_vec()
+}
+
+impl StorageBackend for MmapBackend {
+    fn get(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        let slot = *self.slot_of_key.get(key)?;
+        let range = self.value_range(slot);
+        Some(self.mmap[range].to_vec())
+    }
+
+    fn put(&mut self, key: &[u8], value: &[u8]) {
+        assert!(
+            key.len() <= self.max_key_width,
+            "Key of {} bytes exceeds this backend's max_key_width of {}",
+            key.len(),
+            self.max_key_width
+        );
+        assert_eq!(
+            self.value_width,
+            value.len(),
+            "MmapBackend was opened with value_width {}, got a value of {} bytes",
+            self.value_width,
+            value.len()
+        );
+
+        let slot = match self.slot_of_key.get(key) {
+            Some(&slot) => slot,
+            None => {
+                let slot = self.allocate_slot();
+                self.slot_of_key.insert(key.to_vec(), slot);
+                slot
+            }
+        };
+
+        let slot_width = self.slot_width_of();
+        let offset = slot * slot_width;
+        self.mmap[offset] = OCCUPIED;
+        let key_start = offset + 1;
+        self.mmap[key_start..key_start + self.max_key_width].fill(0);
+        self.mmap[key_start..key_start + key.len()].copy_from_slice(key);
+        let value_range = self.value_range(slot);
+        self.mmap[value_range].copy_from_slice(value);
+    }
+
+    fn delete(&mut self, key: &[u8]) {
+        if let Some(slot) = self.slot_of_key.remove(key) {
+            let offset = slot * self.slot_width_of();
+            self.mmap[offset] = VACANT;
+            self.free_slots.push(slot);
+        }
+    }
+
+    fn write_batch(&mut self, operations: Vec<BatchOperation>) {
+        for operation in operations {
+            match operation {
+                BatchOperation::Put(key, value) => self.put(&key, &value),
+                BatchOperation::Delete(key) => self.delete(&key),
+            }
+        }
+    }
+
+    fn iterate(&mut self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        self.slot_of_key
+            .iter()
+            .map(|(key, &slot)| (key.clone(), self.mmap[self.value_range(slot)].to_vec()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod mmap_backend_tests {
+    use super::*;
+
+    fn unique_temp_file_path(test_name: &str) -> std::path::PathBuf {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let unique = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!(
+            "twenty_first_mmap_backend_{test_name}_{}_{unique}.bin",
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn behaves_like_the_storage_backend_contract() {
+        let path = unique_temp_file_path("contract");
+        let mut backend = MmapBackend::open(&path, 8, 1, 4).unwrap();
+
+        assert_eq!(None, backend.get(b"a"));
+
+        backend.put(b"a", b"1");
+        backend.put(b"b", b"2");
+        assert_eq!(Some(b"1".to_vec()), backend.get(b"a"));
+
+        backend.write_batch(vec![
+            BatchOperation::Put(b"c".to_vec(), b"3".to_vec()),
+            BatchOperation::Delete(b"a".to_vec()),
+        ]);
+        assert_eq!(None, backend.get(b"a"));
+        assert_eq!(Some(b"3".to_vec()), backend.get(b"c"));
+
+        let mut entries = backend.iterate();
+        entries.sort();
+        assert_eq!(
+            vec![
+                (b"b".to_vec(), b"2".to_vec()),
+                (b"c".to_vec(), b"3".to_vec())
+            ],
+            entries
+        );
+
+        backend.delete(b"b");
+        assert_eq!(None, backend.get(b"b"));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn overwriting_an_existing_key_reuses_its_slot() {
+        let path = unique_temp_file_path("overwrite");
+        let mut backend = MmapBackend::open(&path, 8, 4, 4).unwrap();
+
+        backend.put(b"key", b"1234");
+        backend.put(b"key", b"5678");
+        assert_eq!(Some(b"5678".to_vec()), backend.get(b"key"));
+        assert_eq!(1, backend.iterate().len());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn grows_past_its_initial_capacity() {
+        let path = unique_temp_file_path("grow");
+        let mut backend = MmapBackend::open(&path, 8, 8, 2).unwrap();
+
+        for i in 0..100u64 {
+            backend.put(&i.to_le_bytes(), &i.to_le_bytes());
+        }
+        for i in 0..100u64 {
+            assert_eq!(
+                Some(i.to_le_bytes().to_vec()),
+                backend.get(&i.to_le_bytes())
+            );
+        }
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn deleted_slots_are_reused_by_later_puts() {
+        let path = unique_temp_file_path("reuse");
+        let mut backend = MmapBackend::open(&path, 8, 1, 1).unwrap();
+
+        backend.put(b"a", b"1");
+        backend.delete(b"a");
+        backend.put(b"b", b"2");
+
+        assert_eq!(None, backend.get(b"a"));
+        assert_eq!(Some(b"2".to_vec()), backend.get(b"b"));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn reopening_the_file_restores_every_entry() {
+        let path = unique_temp_file_path("reopen");
+        {
+            let mut backend = MmapBackend::open(&path, 8, 4, 4).unwrap();
+            backend.put(b"a", b"1111");
+            backend.put(b"b", b"2222");
+        }
+
+        let mut reopened = MmapBackend::open(&path, 8, 4, 4).unwrap();
+        assert_eq!(Some(b"1111".to_vec()), reopened.get(b"a"));
+        assert_eq!(Some(b"2222".to_vec()), reopened.get(b"b"));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[should_panic = "MmapBackend was opened with value_width 4, got a value of 3 bytes"]
+    #[test]
+    fn put_rejects_a_value_of_the_wrong_width() {
+        let path = unique_temp_file_path("wrong_width");
+        let mut backend = MmapBackend::open(&path, 8, 4, 4).unwrap();
+        backend.put(b"a", b"123");
+    }
+}
@@ -1,6 +1,10 @@
 pub mod archival_mmr;
 pub mod mmr_accumulator;
+pub mod mmr_batch_membership_proof;
+pub mod mmr_consistency_proof;
 pub mod mmr_membership_proof;
+pub mod mmr_proof_wire_format;
 pub mod mmr_trait;
 pub mod shared_advanced;
 pub mod shared_basic;
+pub mod shared_index_math;
@@ -1,6 +1,8 @@
 use std::marker::PhantomData;
 
 use super::mmr_accumulator::MmrAccumulator;
+use super::mmr_batch_membership_proof::MmrBatchMembershipProof;
+use super::mmr_consistency_proof::MmrConsistencyProof;
 use super::mmr_membership_proof::MmrMembershipProof;
 use super::mmr_trait::Mmr;
 use super::{shared_advanced, shared_basic};
@@ -293,6 +295,83 @@ impl<H: AlgebraicHasher, Storage: StorageVec<Digest>> ArchivalMmr<H, Storage> {
         }
     }
 
+    /// Prove membership of several leaves at once, sharing the nodes their authentication paths
+    /// have in common. See [`MmrBatchMembershipProof`].
+    pub fn prove_membership_batch(
+        &self,
+        leaf_indices: &[u64],
+    ) -> (MmrBatchMembershipProof<H>, Vec<Digest>) {
+        let leaf_count = self.count_leaves();
+        assert!(
+            leaf_indices.iter().all(|&li| li < leaf_count),
+            "Cannot prove membership of leaf outside of range."
+        );
+
+        let needed_indices = MmrBatchMembershipProof::<H>::authentication_structure_node_indices(
+            leaf_indices,
+            leaf_count,
+        );
+        let authentication_structure = needed_indices
+            .into_iter()
+            .map(|node_index| self.digests.get(node_index))
+            .collect();
+
+        (
+            MmrBatchMembershipProof::new(leaf_indices.to_vec(), authentication_structure),
+            self.get_peaks(),
+        )
+    }
+
+    /// Append many leaves at once. Equivalent to calling [`Self::append_raw`] once per leaf, but
+    /// avoids the repeated [`Self::prove_membership`] work that the [`Mmr::append`] trait method
+    /// performs for every single leaf. Since `Storage::push` and `Storage::set` only ever queue
+    /// writes (see [`RustyLevelDbVec`]) rather than hitting the database directly, computing all
+    /// of the new peaks and internal nodes here in one pass lets the caller flush every resulting
+    /// write with a single call to [`Self::persist`], instead of one round trip per leaf.
+    pub fn append_batch(&mut self, new_leaves: &[Digest]) {
+        for &new_leaf in new_leaves {
+            self.append_raw(new_leaf);
+        }
+    }
+
+    /// Prove that this MMR, at its current leaf count, is an append-only extension of an earlier
+    /// state of itself that had `old_leaf_count` leaves. See [`MmrConsistencyProof`].
+    pub fn prove_consistency(&self, old_leaf_count: u64) -> MmrConsistencyProof<H> {
+        let new_leaf_count = self.count_leaves();
+        assert!(
+            old_leaf_count <= new_leaf_count,
+            "Cannot prove consistency with a state that has more leaves than the current one."
+        );
+
+        let (_, old_peak_node_indices) =
+            shared_advanced::get_peak_heights_and_peak_node_indices(old_leaf_count);
+        let (_, new_peak_node_indices) =
+            shared_advanced::get_peak_heights_and_peak_node_indices(new_leaf_count);
+        let new_peak_node_indices: std::collections::HashSet<u64> =
+            new_peak_node_indices.into_iter().collect();
+
+        let old_peak_paths = old_peak_node_indices
+            .into_iter()
+            .map(|mut node_index| {
+                let mut path = Vec::new();
+                while !new_peak_node_indices.contains(&node_index) {
+                    let (right_ancestor_count, height) =
+                        shared_advanced::right_lineage_length_and_own_height(node_index);
+                    let sibling_index = if right_ancestor_count != 0 {
+                        shared_advanced::left_sibling(node_index, height)
+                    } else {
+                        shared_advanced::right_sibling(node_index, height)
+                    };
+                    path.push(self.digests.get(sibling_index));
+                    node_index = shared_advanced::parent(node_index);
+                }
+                path
+            })
+            .collect();
+
+        MmrConsistencyProof::new(old_leaf_count, new_leaf_count, old_peak_paths)
+    }
+
     /// Remove the last leaf from the archival MMR
     pub fn remove_last_leaf(&mut self) -> Option<Digest> {
         if self.is_empty() {
@@ -309,10 +388,32 @@ impl<H: AlgebraicHasher, Storage: StorageVec<Digest>> ArchivalMmr<H, Storage> {
 
         Some(ret)
     }
+
+    /// Repeatedly [`Self::remove_last_leaf`] until the archival MMR has exactly `leaf_count`
+    /// leaves, restoring its nodes and peaks to what they were at that point in its history. Useful
+    /// for unwinding an MMR after a chain reorganization discards the leaves appended since
+    /// `leaf_count`, without rebuilding the whole structure from scratch.
+    pub fn rollback_to_leaf_count(&mut self, leaf_count: u64) {
+        assert!(
+            leaf_count <= self.count_leaves(),
+            "Cannot roll back to a leaf count ({leaf_count}) larger than the current one ({}).",
+            self.count_leaves()
+        );
+        while self.count_leaves() > leaf_count {
+            self.remove_last_leaf();
+        }
+    }
 }
 
 impl<H: AlgebraicHasher> ArchivalMmr<H, RustyLevelDbVec<Digest>> {
     /// Add write queue to referenced write batch. Leaves cache and write queue empty.
+    ///
+    /// Every mutation on an `ArchivalMmr<H, RustyLevelDbVec<Digest>>` — [`Self::append_raw`],
+    /// [`Self::append_batch`], [`Self::mutate_leaf_raw`], [`Self::remove_last_leaf`] — only ever
+    /// touches [`RustyLevelDbVec`]'s in-memory write-back cache; none of them talk to LevelDB
+    /// directly. Calling this once after any number of such mutations queues every resulting node
+    /// write into `write_batch` in one go, so a caller building up many nodes (e.g. via
+    /// [`Self::append_batch`]) pays for one LevelDB write, not one per node.
     pub fn persist(&mut self, write_batch: &mut WriteBatch) {
         self.digests.pull_queue(write_batch);
     }
@@ -326,9 +427,11 @@ mod mmr_test {
     use crate::shared_math::other::random_elements;
     use crate::shared_math::tip5::Tip5;
     use crate::test_shared::mmr::{
-        get_empty_rustyleveldb_ammr, get_rustyleveldb_ammr_from_digests,
+        get_empty_ordinary_vec_ammr, get_empty_rustyleveldb_ammr,
+        get_rustyleveldb_ammr_from_digests,
     };
     use crate::util_types::merkle_tree::merkle_tree_test;
+    use crate::util_types::storage_vec::OrdinaryVec;
     use crate::{
         shared_math::b_field_element::BFieldElement,
         util_types::mmr::{
@@ -944,6 +1047,44 @@ mod mmr_test {
         assert_eq!(mmr_big.count_nodes(), mmr_small.count_nodes());
     }
 
+    #[test]
+    fn rollback_to_leaf_count_matches_repeated_remove_last_leaf() {
+        type H = blake3::Hasher;
+
+        let small_size: usize = 100;
+        let big_size: usize = 350;
+        let input_digests_big: Vec<Digest> = random_elements(big_size);
+        let input_digests_small: Vec<Digest> = input_digests_big[0..small_size].to_vec();
+
+        let mut mmr_small: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_rustyleveldb_ammr_from_digests(input_digests_small);
+        let mut mmr_big: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_rustyleveldb_ammr_from_digests(input_digests_big);
+
+        mmr_big.rollback_to_leaf_count(small_size as u64);
+
+        assert_eq!(mmr_big.get_peaks(), mmr_small.get_peaks());
+        assert_eq!(mmr_big.bag_peaks(), mmr_small.bag_peaks());
+        assert_eq!(mmr_big.count_leaves(), mmr_small.count_leaves());
+        assert_eq!(mmr_big.count_nodes(), mmr_small.count_nodes());
+
+        // Rolling back to the current leaf count is a no-op.
+        let peaks_before = mmr_small.get_peaks();
+        mmr_small.rollback_to_leaf_count(small_size as u64);
+        assert_eq!(peaks_before, mmr_small.get_peaks());
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot roll back")]
+    fn rollback_to_leaf_count_panics_on_leaf_count_increase() {
+        type H = blake3::Hasher;
+
+        let input_digests: Vec<Digest> = random_elements(5);
+        let mut mmr: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_rustyleveldb_ammr_from_digests(input_digests);
+        mmr.rollback_to_leaf_count(6);
+    }
+
     #[test]
     fn variable_size_blake3_mmr_test() {
         type H = blake3::Hasher;
@@ -1069,4 +1210,115 @@ mod mmr_test {
         assert_eq!(digest0, ammr0.get_leaf(0));
         assert_eq!(digest1, ammr1.get_leaf(0));
     }
+
+    #[test]
+    fn append_batch_matches_repeated_single_append() {
+        type H = blake3::Hasher;
+
+        let new_leaves: Vec<Digest> = random_elements(5);
+
+        let mut one_at_a_time: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_empty_rustyleveldb_ammr();
+        for &leaf in &new_leaves {
+            one_at_a_time.append(leaf);
+        }
+
+        let mut batched: ArchivalMmr<H, RustyLevelDbVec<Digest>> = get_empty_rustyleveldb_ammr();
+        batched.append_batch(&new_leaves);
+
+        assert_eq!(one_at_a_time.get_peaks(), batched.get_peaks());
+        assert_eq!(one_at_a_time.count_leaves(), batched.count_leaves());
+        for i in 0..new_leaves.len() as u64 {
+            assert_eq!(one_at_a_time.get_leaf(i), batched.get_leaf(i));
+        }
+    }
+
+    #[test]
+    fn append_batch_on_nonempty_mmr_extends_existing_leaves() {
+        type H = blake3::Hasher;
+
+        let initial_leaves: Vec<Digest> = random_elements(3);
+        let mut mmr = get_rustyleveldb_ammr_from_digests::<H>(initial_leaves.clone());
+
+        let new_leaves: Vec<Digest> = random_elements(4);
+        mmr.append_batch(&new_leaves);
+
+        let mut expected: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_rustyleveldb_ammr_from_digests::<H>(initial_leaves);
+        for &leaf in &new_leaves {
+            expected.append(leaf);
+        }
+
+        assert_eq!(expected.get_peaks(), mmr.get_peaks());
+        assert_eq!(expected.count_leaves(), mmr.count_leaves());
+    }
+
+    #[test]
+    fn append_batch_defers_all_writes_until_a_single_persist() {
+        type H = blake3::Hasher;
+
+        let opt = rusty_leveldb::in_memory();
+        let db = DB::open("mydatabase", opt).unwrap();
+        let db = Arc::new(Mutex::new(db));
+        let persistent_vec = RustyLevelDbVec::new(db.clone(), 0, "archival MMR for unit tests");
+        let mut ammr: ArchivalMmr<H, RustyLevelDbVec<Digest>> = ArchivalMmr::new(persistent_vec);
+
+        let new_leaves: Vec<Digest> = random_elements(10);
+        ammr.append_batch(&new_leaves);
+
+        // Nothing has touched the database yet: every node produced by the batch append only
+        // lives in the write-back cache and write queue.
+        let mut db_iter = db.lock().unwrap().new_iter().unwrap();
+        assert!(db_iter.next().is_none());
+
+        // One call to `persist`, and one write to the database, is enough to flush every node
+        // the batch append produced.
+        let mut write_batch = WriteBatch::new();
+        ammr.persist(&mut write_batch);
+        db.lock().unwrap().write(write_batch, true).unwrap();
+
+        db_iter = db.lock().unwrap().new_iter().unwrap();
+        assert!(db_iter.next().is_some());
+        for (i, &leaf) in new_leaves.iter().enumerate() {
+            assert_eq!(leaf, ammr.get_leaf(i as u64));
+        }
+    }
+
+    #[test]
+    fn ordinary_vec_backed_ammr_agrees_with_the_rustyleveldb_backed_one() {
+        // `ArchivalMmr` is generic over `Storage: StorageVec<Digest>`; this exercises it with
+        // `OrdinaryVec`, an in-memory `Vec`-backed `StorageVec`, to confirm the exact same
+        // sequence of operations produces the exact same result as the on-disk backend, with no
+        // database or temp files involved.
+        type H = Tip5;
+
+        let leaves: Vec<Digest> = random_elements(13);
+        let mut in_memory: ArchivalMmr<H, OrdinaryVec<Digest>> = get_empty_ordinary_vec_ammr();
+        let mut on_disk: ArchivalMmr<H, RustyLevelDbVec<Digest>> = get_empty_rustyleveldb_ammr();
+
+        for &leaf in &leaves {
+            in_memory.append(leaf);
+            on_disk.append(leaf);
+        }
+        assert_eq!(on_disk.get_peaks(), in_memory.get_peaks());
+        assert_eq!(on_disk.bag_peaks(), in_memory.bag_peaks());
+        assert_eq!(on_disk.count_leaves(), in_memory.count_leaves());
+
+        for i in 0..leaves.len() as u64 {
+            assert_eq!(on_disk.get_leaf(i), in_memory.get_leaf(i));
+            assert_eq!(
+                on_disk.prove_membership(i).0,
+                in_memory.prove_membership(i).0
+            );
+        }
+
+        let new_leaf = random();
+        on_disk.mutate_leaf_raw(3, new_leaf);
+        in_memory.mutate_leaf_raw(3, new_leaf);
+        assert_eq!(on_disk.get_peaks(), in_memory.get_peaks());
+
+        assert_eq!(Some(on_disk.get_leaf(12)), on_disk.remove_last_leaf());
+        assert_eq!(Some(in_memory.get_leaf(12)), in_memory.remove_last_leaf());
+        assert_eq!(on_disk.get_peaks(), in_memory.get_peaks());
+    }
 }
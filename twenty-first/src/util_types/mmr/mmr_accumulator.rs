@@ -51,6 +51,16 @@ where
 }
 
 impl<H: AlgebraicHasher> MmrAccumulator<H> {
+    /// A cheap, zero-copy accessor for the current peaks. Unlike [`Mmr::get_peaks`], which is
+    /// forced by the trait signature to return an owned `Vec`, this just borrows the field: `peaks`
+    /// is not recomputed from `leaf_count` on every call, it is the accumulator's sole
+    /// representation of its own state and is kept up to date incrementally by
+    /// [`Mmr::append`](Mmr::append) and [`Mmr::mutate_leaf`](Mmr::mutate_leaf), each of which only
+    /// touches the peaks affected by that single operation.
+    pub fn peaks(&self) -> &[Digest] {
+        &self.peaks
+    }
+
     pub fn init(peaks: Vec<Digest>, leaf_count: u64) -> Self {
         Self {
             leaf_count,
@@ -663,6 +673,21 @@ mod accumulator_mmr_tests {
         }
     }
 
+    #[test]
+    fn peaks_accessor_tracks_incremental_updates_from_append_and_mutate() {
+        type H = Tip5;
+
+        let mut mmra: MmrAccumulator<H> = MmrAccumulator::new(vec![]);
+        for leaf in random_elements::<Digest>(10) {
+            let membership_proof = mmra.append(leaf);
+            assert_eq!(mmra.get_peaks(), mmra.peaks());
+
+            let new_leaf: Digest = random();
+            mmra.mutate_leaf(&membership_proof, &new_leaf);
+            assert_eq!(mmra.get_peaks(), mmra.peaks());
+        }
+    }
+
     #[test]
     fn mmra_serialization_test() {
         // You could argue that this test doesn't belong here, as it tests the behavior of
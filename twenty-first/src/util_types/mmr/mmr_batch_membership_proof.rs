@@ -0,0 +1,464 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::marker::PhantomData;
+
+use serde::{Deserialize, Serialize};
+
+use super::mmr_membership_proof::MmrMembershipProof;
+use super::shared_advanced;
+use super::shared_basic;
+use crate::shared_math::digest::Digest;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
+use crate::utils::has_unique_elements;
+
+/// The union, across all queried leaves, of the node indices on their direct paths up to (but
+/// not including) their peak, and of the indices that need to be authenticated to derive the
+/// nodes on those direct paths, along with each peak's position in the MMR's peak list.
+struct NodeSets {
+    /// Node indices reachable by climbing from a queried leaf to its peak (leaves included).
+    /// [`shared_advanced::parent`] always maps a node index to a strictly greater one, so this
+    /// set can be processed in ascending order to derive every digest in it bottom-up.
+    on_a_direct_path: HashSet<u64>,
+    /// The node indices that must be supplied explicitly, i.e. that are not in
+    /// `on_a_direct_path`, sorted ascending for a canonical encoding.
+    needed: Vec<u64>,
+    peak_position_of: HashMap<u64, usize>,
+}
+
+/// A node is only needed if it cannot be derived from the queried leaves and the other needed
+/// nodes: every node on the direct path from a queried leaf up to its peak is derivable, so only
+/// the *siblings* of those direct-path nodes that are not themselves on some other queried leaf's
+/// direct path have to be supplied.
+fn compute_node_sets(leaf_indices: &[u64], leaf_count: u64) -> NodeSets {
+    let (_, peak_node_indices) =
+        shared_advanced::get_peak_heights_and_peak_node_indices(leaf_count);
+    let peak_position_of: HashMap<u64, usize> = peak_node_indices
+        .into_iter()
+        .enumerate()
+        .map(|(position, node_index)| (node_index, position))
+        .collect();
+
+    let mut on_a_direct_path: HashSet<u64> = HashSet::new();
+    let mut node_is_needed: HashSet<u64> = HashSet::new();
+    for &leaf_index in leaf_indices {
+        let mut node_index = shared_advanced::leaf_index_to_node_index(leaf_index);
+        while !peak_position_of.contains_key(&node_index) {
+            let (right_ancestor_count, height) =
+                shared_advanced::right_lineage_length_and_own_height(node_index);
+            let sibling_index = if right_ancestor_count != 0 {
+                shared_advanced::left_sibling(node_index, height)
+            } else {
+                shared_advanced::right_sibling(node_index, height)
+            };
+            on_a_direct_path.insert(node_index);
+            node_is_needed.insert(sibling_index);
+            node_index = shared_advanced::parent(node_index);
+        }
+    }
+
+    let mut needed: Vec<u64> = node_is_needed
+        .difference(&on_a_direct_path)
+        .copied()
+        .collect();
+    needed.sort_unstable();
+
+    NodeSets {
+        on_a_direct_path,
+        needed,
+        peak_position_of,
+    }
+}
+
+/// Given `node_index`, the node index of its sibling and which of the two is the left child.
+fn sibling(node_index: u64) -> (u64, u64) {
+    let (right_ancestor_count, height) =
+        shared_advanced::right_lineage_length_and_own_height(node_index);
+    if right_ancestor_count != 0 {
+        (
+            shared_advanced::left_sibling(node_index, height),
+            node_index,
+        )
+    } else {
+        (
+            node_index,
+            shared_advanced::right_sibling(node_index, height),
+        )
+    }
+}
+
+/// A proof that several leaves, at once, are members of an MMR with a given set of peaks,
+/// deduplicating the nodes shared between the individual leaves' authentication paths.
+///
+/// Where sending one [`MmrMembershipProof`](super::mmr_membership_proof::MmrMembershipProof) per
+/// leaf repeats every node on the leaves' shared ancestry once per leaf, a
+/// [`MmrBatchMembershipProof`] includes each such node exactly once, which shrinks the proof for
+/// consumers (such as wallets) that track many leaves clustered together in the same MMR.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MmrBatchMembershipProof<H>
+where
+    H: AlgebraicHasher,
+{
+    pub leaf_indices: Vec<u64>,
+    pub authentication_structure: Vec<Digest>,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: AlgebraicHasher> PartialEq for MmrBatchMembershipProof<H> {
+    fn eq(&self, other: &Self) -> bool {
+        self.leaf_indices == other.leaf_indices
+            && self.authentication_structure == other.authentication_structure
+    }
+}
+
+impl<H: AlgebraicHasher> Eq for MmrBatchMembershipProof<H> {}
+
+impl<H: AlgebraicHasher> MmrBatchMembershipProof<H> {
+    pub fn new(leaf_indices: Vec<u64>, authentication_structure: Vec<Digest>) -> Self {
+        assert!(
+            has_unique_elements(leaf_indices.iter().copied()),
+            "Duplicated leaf indices are not allowed in a batch membership proof"
+        );
+        Self {
+            leaf_indices,
+            authentication_structure,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// The node indices that a batch membership proof for `leaf_indices` must contain, given an
+    /// MMR of `leaf_count` leaves. Exposed so that a prover can compute exactly the digests a
+    /// verifier will expect, in the same order.
+    pub fn authentication_structure_node_indices(
+        leaf_indices: &[u64],
+        leaf_count: u64,
+    ) -> Vec<u64> {
+        compute_node_sets(leaf_indices, leaf_count).needed
+    }
+
+    /// Merge a set of individually-obtained [`MmrMembershipProof`]s into one compact aggregate,
+    /// dropping any authentication-path node that more than one of the input proofs supplies. A
+    /// light client that has collected proofs for several leaves one at a time can call this
+    /// before shipping them onward, in order to only transmit each shared node once.
+    pub fn from_membership_proofs(
+        leaf_indices: &[u64],
+        membership_proofs: &[MmrMembershipProof<H>],
+        leaf_count: u64,
+    ) -> Self {
+        assert_eq!(
+            leaf_indices.len(),
+            membership_proofs.len(),
+            "Must supply exactly one membership proof per leaf index"
+        );
+
+        let mut known: HashMap<u64, Digest> = HashMap::new();
+        for mp in membership_proofs {
+            for (node_index, digest) in mp
+                .get_node_indices()
+                .into_iter()
+                .zip(&mp.authentication_path)
+            {
+                known.insert(node_index, *digest);
+            }
+        }
+
+        let needed = compute_node_sets(leaf_indices, leaf_count).needed;
+        let authentication_structure = needed.iter().map(|node_index| known[node_index]).collect();
+
+        Self::new(leaf_indices.to_vec(), authentication_structure)
+    }
+
+    /// Re-expand this aggregate back into one [`MmrMembershipProof`] per leaf, for consumers that
+    /// still expect the single-leaf proof format. `leaf_digests` must be given in the same order
+    /// as `self.leaf_indices`. Returns `None` if the aggregate is malformed, e.g. because it does
+    /// not contain enough authentication-structure digests to reconstruct every path.
+    pub fn expand(
+        &self,
+        leaf_digests: &[Digest],
+        leaf_count: u64,
+    ) -> Option<Vec<MmrMembershipProof<H>>> {
+        if self.leaf_indices.len() != leaf_digests.len() {
+            return None;
+        }
+
+        let node_sets = compute_node_sets(&self.leaf_indices, leaf_count);
+        if node_sets.needed.len() != self.authentication_structure.len() {
+            return None;
+        }
+
+        let mut digests: HashMap<u64, Digest> = HashMap::new();
+        for (&leaf_index, &leaf_digest) in self.leaf_indices.iter().zip(leaf_digests) {
+            digests.insert(
+                shared_advanced::leaf_index_to_node_index(leaf_index),
+                leaf_digest,
+            );
+        }
+        for (&node_index, &digest) in node_sets.needed.iter().zip(&self.authentication_structure) {
+            digests.insert(node_index, digest);
+        }
+
+        // Same bottom-up derivation as `verify`; see the comment there.
+        let mut ascending: Vec<u64> = node_sets.on_a_direct_path.iter().copied().collect();
+        ascending.sort_unstable();
+        for node_index in ascending {
+            if digests.contains_key(&node_index) {
+                continue;
+            }
+            let (_, own_height) = shared_advanced::right_lineage_length_and_own_height(node_index);
+            let left_index = shared_basic::left_child(node_index, own_height);
+            let right_index = shared_basic::right_child(node_index);
+            let left_digest = *digests.get(&left_index)?;
+            let right_digest = *digests.get(&right_index)?;
+            digests.insert(node_index, H::hash_pair(&left_digest, &right_digest));
+        }
+
+        let mut expanded = Vec::with_capacity(self.leaf_indices.len());
+        for &leaf_index in &self.leaf_indices {
+            let mut node_index = shared_advanced::leaf_index_to_node_index(leaf_index);
+            let mut authentication_path = Vec::new();
+            while !node_sets.peak_position_of.contains_key(&node_index) {
+                let (left_index, right_index) = sibling(node_index);
+                let left_digest = *digests.get(&left_index)?;
+                let right_digest = *digests.get(&right_index)?;
+                authentication_path.push(if left_index == node_index {
+                    right_digest
+                } else {
+                    left_digest
+                });
+                let parent_index = shared_advanced::parent(node_index);
+                digests.insert(parent_index, H::hash_pair(&left_digest, &right_digest));
+                node_index = parent_index;
+            }
+            expanded.push(MmrMembershipProof::new(leaf_index, authentication_path));
+        }
+
+        Some(expanded)
+    }
+
+    /// Verify that `leaf_digests` (in the same order as `self.leaf_indices`) are all members of
+    /// the MMR with the given `peaks` and `leaf_count`.
+    pub fn verify(&self, peaks: &[Digest], leaf_digests: &[Digest], leaf_count: u64) -> bool {
+        if self.leaf_indices.len() != leaf_digests.len() {
+            return false;
+        }
+        if !self.leaf_indices.iter().all(|&li| li < leaf_count) {
+            return false;
+        }
+
+        let node_sets = compute_node_sets(&self.leaf_indices, leaf_count);
+        if node_sets.needed.len() != self.authentication_structure.len() {
+            return false;
+        }
+        if node_sets.peak_position_of.len() != peaks.len() {
+            return false;
+        }
+
+        let mut digests: HashMap<u64, Digest> = HashMap::new();
+        for (&leaf_index, &leaf_digest) in self.leaf_indices.iter().zip(leaf_digests) {
+            digests.insert(
+                shared_advanced::leaf_index_to_node_index(leaf_index),
+                leaf_digest,
+            );
+        }
+        for (&node_index, &digest) in node_sets.needed.iter().zip(&self.authentication_structure) {
+            digests.insert(node_index, digest);
+        }
+
+        // Derive every direct-path node bottom-up. `parent()` always maps to a strictly greater
+        // node index, so processing in ascending order guarantees a node's children (whether
+        // queried leaves, supplied authentication-structure digests, or derived earlier in this
+        // very loop) are already in `digests` by the time the node itself is processed.
+        let mut ascending: Vec<u64> = node_sets.on_a_direct_path.iter().copied().collect();
+        ascending.sort_unstable();
+        for node_index in ascending {
+            if digests.contains_key(&node_index) {
+                continue; // one of the queried leaves
+            }
+            let (_, own_height) = shared_advanced::right_lineage_length_and_own_height(node_index);
+            let left_index = shared_basic::left_child(node_index, own_height);
+            let right_index = shared_basic::right_child(node_index);
+            let (Some(&left_digest), Some(&right_digest)) =
+                (digests.get(&left_index), digests.get(&right_index))
+            else {
+                return false;
+            };
+            digests.insert(node_index, H::hash_pair(&left_digest, &right_digest));
+        }
+
+        for &leaf_index in &self.leaf_indices {
+            let mut node_index = shared_advanced::leaf_index_to_node_index(leaf_index);
+            while !node_sets.peak_position_of.contains_key(&node_index) {
+                let (left_index, right_index) = sibling(node_index);
+                let (Some(&left_digest), Some(&right_digest)) =
+                    (digests.get(&left_index), digests.get(&right_index))
+                else {
+                    return false;
+                };
+                let parent_digest = H::hash_pair(&left_digest, &right_digest);
+                let parent_index = shared_advanced::parent(node_index);
+                digests.insert(parent_index, parent_digest);
+                node_index = parent_index;
+            }
+
+            if digests[&node_index] != peaks[node_sets.peak_position_of[&node_index]] {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod mmr_batch_membership_proof_tests {
+    use crate::shared_math::other::random_elements;
+    use crate::shared_math::tip5::Tip5;
+    use crate::test_shared::mmr::get_rustyleveldb_ammr_from_digests;
+    use crate::util_types::mmr::archival_mmr::ArchivalMmr;
+    use crate::util_types::mmr::mmr_trait::Mmr;
+    use crate::util_types::storage_vec::RustyLevelDbVec;
+
+    use super::*;
+
+    #[test]
+    fn batch_proof_for_clustered_leaves_is_smaller_than_separate_proofs() {
+        type H = Tip5;
+
+        let leaves: Vec<Digest> = random_elements(16);
+        let mmr: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_rustyleveldb_ammr_from_digests(leaves.clone());
+
+        let queried_leaf_indices = vec![3u64, 4, 5];
+        let (batch_proof, peaks) = mmr.prove_membership_batch(&queried_leaf_indices);
+        let queried_leaf_digests: Vec<Digest> = queried_leaf_indices
+            .iter()
+            .map(|&i| leaves[i as usize])
+            .collect();
+
+        assert!(batch_proof.verify(&peaks, &queried_leaf_digests, mmr.count_leaves()));
+
+        let separate_proof_size: usize = queried_leaf_indices
+            .iter()
+            .map(|&i| mmr.prove_membership(i).0.authentication_path.len())
+            .sum();
+        assert!(batch_proof.authentication_structure.len() < separate_proof_size);
+    }
+
+    #[test]
+    fn batch_proof_round_trips_for_every_leaf_subset() {
+        type H = Tip5;
+
+        let leaves: Vec<Digest> = random_elements(8);
+        let mmr: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_rustyleveldb_ammr_from_digests(leaves.clone());
+        let peaks = mmr.get_peaks();
+
+        for subset in [
+            vec![0u64],
+            vec![0, 1],
+            vec![0, 7],
+            vec![1, 2, 3],
+            (0..8).collect::<Vec<_>>(),
+        ] {
+            let (batch_proof, batch_proof_peaks) = mmr.prove_membership_batch(&subset);
+            assert_eq!(peaks, batch_proof_peaks);
+            let leaf_digests: Vec<Digest> = subset.iter().map(|&i| leaves[i as usize]).collect();
+            assert!(batch_proof.verify(&peaks, &leaf_digests, mmr.count_leaves()));
+        }
+    }
+
+    #[test]
+    fn tampered_leaf_digest_fails_batch_verification() {
+        type H = Tip5;
+
+        let leaves: Vec<Digest> = random_elements(8);
+        let mmr: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_rustyleveldb_ammr_from_digests(leaves.clone());
+        let (batch_proof, peaks) = mmr.prove_membership_batch(&[2, 5]);
+
+        let wrong_leaf: Digest = random_elements(1)[0];
+        assert!(!batch_proof.verify(&peaks, &[wrong_leaf, leaves[5]], mmr.count_leaves()));
+    }
+
+    #[test]
+    fn tampered_authentication_structure_fails_batch_verification() {
+        type H = Tip5;
+
+        let leaves: Vec<Digest> = random_elements(8);
+        let mmr: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_rustyleveldb_ammr_from_digests(leaves.clone());
+        let (mut batch_proof, peaks) = mmr.prove_membership_batch(&[2, 5]);
+        assert!(!batch_proof.authentication_structure.is_empty());
+        batch_proof.authentication_structure[0] = random_elements(1)[0];
+
+        let leaf_digests = vec![leaves[2], leaves[5]];
+        assert!(!batch_proof.verify(&peaks, &leaf_digests, mmr.count_leaves()));
+    }
+
+    #[test]
+    fn aggregating_individual_proofs_matches_direct_batch_proof() {
+        type H = Tip5;
+
+        let leaves: Vec<Digest> = random_elements(13);
+        let mmr: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_rustyleveldb_ammr_from_digests(leaves.clone());
+        let leaf_count = mmr.count_leaves();
+
+        let leaf_indices = vec![1u64, 2, 9, 11];
+        let individual_proofs: Vec<_> = leaf_indices
+            .iter()
+            .map(|&i| mmr.prove_membership(i).0)
+            .collect();
+
+        let aggregated = MmrBatchMembershipProof::from_membership_proofs(
+            &leaf_indices,
+            &individual_proofs,
+            leaf_count,
+        );
+        let (direct, peaks) = mmr.prove_membership_batch(&leaf_indices);
+        assert_eq!(direct, aggregated);
+
+        let leaf_digests: Vec<Digest> = leaf_indices.iter().map(|&i| leaves[i as usize]).collect();
+        assert!(aggregated.verify(&peaks, &leaf_digests, leaf_count));
+    }
+
+    #[test]
+    fn expanding_an_aggregate_reproduces_the_original_proofs() {
+        type H = Tip5;
+
+        let leaves: Vec<Digest> = random_elements(13);
+        let mmr: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_rustyleveldb_ammr_from_digests(leaves.clone());
+        let leaf_count = mmr.count_leaves();
+
+        let leaf_indices = vec![1u64, 2, 9, 11];
+        let original_proofs: Vec<_> = leaf_indices
+            .iter()
+            .map(|&i| mmr.prove_membership(i).0)
+            .collect();
+        let leaf_digests: Vec<Digest> = leaf_indices.iter().map(|&i| leaves[i as usize]).collect();
+
+        let (aggregated, peaks) = mmr.prove_membership_batch(&leaf_indices);
+        let expanded = aggregated.expand(&leaf_digests, leaf_count).unwrap();
+
+        assert_eq!(original_proofs, expanded);
+        for (i, mp) in leaf_indices.iter().zip(&expanded) {
+            assert!(mp.verify(&peaks, &leaves[*i as usize], leaf_count).0);
+        }
+    }
+
+    #[test]
+    fn expand_fails_gracefully_on_malformed_aggregate() {
+        type H = Tip5;
+
+        let leaves: Vec<Digest> = random_elements(8);
+        let mmr: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_rustyleveldb_ammr_from_digests(leaves.clone());
+        let (mut aggregated, _) = mmr.prove_membership_batch(&[0, 4]);
+        aggregated.authentication_structure.pop();
+
+        let leaf_digests = vec![leaves[0], leaves[4]];
+        assert!(aggregated
+            .expand(&leaf_digests, mmr.count_leaves())
+            .is_none());
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use serde::{Deserialize, Serialize};
+
+use super::shared_advanced;
+use crate::shared_math::digest::Digest;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
+
+/// A proof that an MMR accumulator with `new_leaf_count` leaves is an append-only extension of an
+/// earlier accumulator with `old_leaf_count` leaves, without requiring the verifier to know any of
+/// the leaves involved.
+///
+/// Every peak of the old accumulator either survives unchanged as a peak of the new one, or is
+/// absorbed into a larger peak as further leaves were appended. [`MmrConsistencyProof`] carries,
+/// for each old peak in turn, the authentication path of sibling digests needed to climb from that
+/// peak up to the new peak that is its ancestor (an empty path if the old peak is untouched).
+/// A light client that already trusts `(old_peaks, old_leaf_count)` can use this to adopt
+/// `(new_peaks, new_leaf_count)` without replaying any of the leaves appended in between.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MmrConsistencyProof<H>
+where
+    H: AlgebraicHasher,
+{
+    pub old_leaf_count: u64,
+    pub new_leaf_count: u64,
+    pub old_peak_paths: Vec<Vec<Digest>>,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: AlgebraicHasher> PartialEq for MmrConsistencyProof<H> {
+    fn eq(&self, other: &Self) -> bool {
+        self.old_leaf_count == other.old_leaf_count
+            && self.new_leaf_count == other.new_leaf_count
+            && self.old_peak_paths == other.old_peak_paths
+    }
+}
+
+impl<H: AlgebraicHasher> Eq for MmrConsistencyProof<H> {}
+
+impl<H: AlgebraicHasher> MmrConsistencyProof<H> {
+    pub fn new(old_leaf_count: u64, new_leaf_count: u64, old_peak_paths: Vec<Vec<Digest>>) -> Self {
+        assert!(
+            old_leaf_count <= new_leaf_count,
+            "An MMR can only grow, so it cannot be consistent with a smaller one."
+        );
+        Self {
+            old_leaf_count,
+            new_leaf_count,
+            old_peak_paths,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Verify that `new_peaks` (for `self.new_leaf_count` leaves) is an append-only extension of
+    /// `old_peaks` (for `self.old_leaf_count` leaves).
+    pub fn verify(&self, old_peaks: &[Digest], new_peaks: &[Digest]) -> bool {
+        let (_, old_peak_node_indices) =
+            shared_advanced::get_peak_heights_and_peak_node_indices(self.old_leaf_count);
+        if old_peak_node_indices.len() != old_peaks.len()
+            || old_peak_node_indices.len() != self.old_peak_paths.len()
+        {
+            return false;
+        }
+
+        let (_, new_peak_node_indices) =
+            shared_advanced::get_peak_heights_and_peak_node_indices(self.new_leaf_count);
+        if new_peak_node_indices.len() != new_peaks.len() {
+            return false;
+        }
+        let new_peak_position_of: HashMap<u64, usize> = new_peak_node_indices
+            .into_iter()
+            .enumerate()
+            .map(|(position, node_index)| (node_index, position))
+            .collect();
+
+        for ((&old_peak_node_index, &old_peak_digest), path) in old_peak_node_indices
+            .iter()
+            .zip(old_peaks)
+            .zip(&self.old_peak_paths)
+        {
+            let mut node_index = old_peak_node_index;
+            let mut acc = old_peak_digest;
+            for &sibling_digest in path {
+                let (right_ancestor_count, _) =
+                    shared_advanced::right_lineage_length_and_own_height(node_index);
+                acc = if right_ancestor_count != 0 {
+                    // node is a right child, so the sibling supplied is its left sibling
+                    H::hash_pair(&sibling_digest, &acc)
+                } else {
+                    // node is a left child, so the sibling supplied is its right sibling
+                    H::hash_pair(&acc, &sibling_digest)
+                };
+                node_index = shared_advanced::parent(node_index);
+            }
+
+            let Some(&position) = new_peak_position_of.get(&node_index) else {
+                return false;
+            };
+            if new_peaks[position] != acc {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod mmr_consistency_proof_tests {
+    use crate::shared_math::other::random_elements;
+    use crate::shared_math::tip5::Tip5;
+    use crate::test_shared::mmr::get_rustyleveldb_ammr_from_digests;
+    use crate::util_types::mmr::archival_mmr::ArchivalMmr;
+    use crate::util_types::mmr::mmr_trait::Mmr;
+    use crate::util_types::storage_vec::RustyLevelDbVec;
+
+    use super::*;
+
+    #[test]
+    fn consistency_proof_holds_across_a_range_of_growth_steps() {
+        type H = Tip5;
+
+        let leaves: Vec<Digest> = random_elements(20);
+        for old_leaf_count in 0..=20u64 {
+            for new_leaf_count in old_leaf_count..=20u64 {
+                let old_mmr: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+                    get_rustyleveldb_ammr_from_digests(leaves[..old_leaf_count as usize].to_vec());
+                let old_peaks = old_mmr.get_peaks();
+
+                let new_mmr: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+                    get_rustyleveldb_ammr_from_digests(leaves[..new_leaf_count as usize].to_vec());
+                let new_peaks = new_mmr.get_peaks();
+
+                let proof = new_mmr.prove_consistency(old_leaf_count);
+                assert!(
+                    proof.verify(&old_peaks, &new_peaks),
+                    "old_leaf_count={old_leaf_count}, new_leaf_count={new_leaf_count}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn tampered_new_peak_fails_consistency_verification() {
+        type H = Tip5;
+
+        let leaves: Vec<Digest> = random_elements(10);
+        let old_mmr: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_rustyleveldb_ammr_from_digests(leaves[..3].to_vec());
+        let old_peaks = old_mmr.get_peaks();
+
+        let new_mmr: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_rustyleveldb_ammr_from_digests(leaves.clone());
+        let mut new_peaks = new_mmr.get_peaks();
+        new_peaks[0] = random_elements(1)[0];
+
+        let proof = new_mmr.prove_consistency(3);
+        assert!(!proof.verify(&old_peaks, &new_peaks));
+    }
+
+    #[test]
+    fn tampered_old_peak_fails_consistency_verification() {
+        type H = Tip5;
+
+        let leaves: Vec<Digest> = random_elements(10);
+        let old_mmr: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_rustyleveldb_ammr_from_digests(leaves[..3].to_vec());
+        let mut old_peaks = old_mmr.get_peaks();
+        old_peaks[0] = random_elements(1)[0];
+
+        let new_mmr: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_rustyleveldb_ammr_from_digests(leaves.clone());
+        let new_peaks = new_mmr.get_peaks();
+
+        let proof = new_mmr.prove_consistency(3);
+        assert!(!proof.verify(&old_peaks, &new_peaks));
+    }
+
+    #[test]
+    fn consistency_proof_against_wrong_old_leaf_count_fails() {
+        type H = Tip5;
+
+        let leaves: Vec<Digest> = random_elements(10);
+        let old_mmr: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_rustyleveldb_ammr_from_digests(leaves[..3].to_vec());
+        let old_peaks = old_mmr.get_peaks();
+
+        let new_mmr: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_rustyleveldb_ammr_from_digests(leaves.clone());
+        let new_peaks = new_mmr.get_peaks();
+
+        let proof = new_mmr.prove_consistency(4);
+        assert!(!proof.verify(&old_peaks, &new_peaks));
+    }
+}
@@ -7,11 +7,13 @@ use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
 use std::{fmt::Debug, iter::FromIterator};
 
+use super::mmr_trait::MmrError;
 use super::{shared_advanced, shared_basic};
 use crate::shared_math::bfield_codec::BFieldCodec;
 use crate::shared_math::digest::Digest;
 use crate::shared_math::other::log_2_floor;
 use crate::util_types::algebraic_hasher::AlgebraicHasher;
+use crate::util_types::compact_authentication_path::CompactAuthenticationPath;
 
 #[derive(Debug, Clone, Serialize, Deserialize, GetSize)]
 pub struct MmrMembershipProof<H>
@@ -70,9 +72,19 @@ impl<H: AlgebraicHasher> MmrMembershipProof<H> {
         }
     }
 
+    /// Pack this proof's `authentication_path` into a [`CompactAuthenticationPath`], for a wire
+    /// format that does not require also transmitting `leaf_index` alongside it.
+    pub fn compact_authentication_path(&self, leaf_count: u64) -> CompactAuthenticationPath {
+        let (mt_index, _) =
+            shared_basic::leaf_index_to_mt_index_and_peak_index(self.leaf_index, leaf_count);
+        CompactAuthenticationPath::from_node_index(mt_index, self.authentication_path.clone())
+    }
+
     /**
      * verify
      * Verify a membership proof for an MMR. If verification succeeds, return the final state of the accumulator hash.
+     * Operates entirely on borrowed slices and `Copy` digests, so verifying a batch of proofs in a loop allocates
+     * nothing beyond what the caller already holds.
      */
     pub fn verify(
         &self,
@@ -113,6 +125,49 @@ impl<H: AlgebraicHasher> MmrMembershipProof<H> {
         (true, Some(acc_hash))
     }
 
+    /// Fallible alternative to [`Self::verify`]: on success, return the accumulator hash; on
+    /// failure, report which of the distinguishable ways verification can fail actually happened,
+    /// instead of collapsing them all into `false`.
+    pub fn try_verify(
+        &self,
+        peaks: &[Digest],
+        leaf_hash: &Digest,
+        leaf_count: u64,
+    ) -> Result<Digest, MmrError> {
+        let (mut mt_index, peak_index) =
+            shared_basic::leaf_index_to_mt_index_and_peak_index(self.leaf_index, leaf_count);
+
+        if log_2_floor(mt_index as u128) != self.authentication_path.len() as u64 {
+            return Err(MmrError::AuthenticationPathLengthMismatch);
+        }
+
+        let Some(expected_peak) = peaks.get(peak_index as usize) else {
+            return Err(MmrError::PeakIndexOutOfBounds);
+        };
+
+        let mut i = 0;
+        let mut acc_hash: Digest = leaf_hash.to_owned();
+        while mt_index != 1 {
+            let ap_element = self.authentication_path[i];
+            if mt_index % 2 == 0 {
+                // node of `acc_hash` is left child
+                acc_hash = H::hash_pair(&acc_hash, &ap_element);
+            } else {
+                // node of `acc_hash` is right child
+                acc_hash = H::hash_pair(&ap_element, &acc_hash);
+            }
+
+            i += 1;
+            mt_index /= 2;
+        }
+
+        if *expected_peak != acc_hash {
+            return Err(MmrError::PeakMismatch);
+        }
+
+        Ok(acc_hash)
+    }
+
     /// Return the node indices for the authentication path in this membership proof
     pub fn get_node_indices(&self) -> Vec<u64> {
         let mut node_index = shared_advanced::leaf_index_to_node_index(self.leaf_index);
@@ -622,6 +677,74 @@ mod mmr_membership_proof_test {
 
     use super::*;
 
+    #[test]
+    fn compact_authentication_path_directions_match_verification_directions() {
+        type H = blake3::Hasher;
+
+        let leaf_count = 25;
+        let leaf_digests: Vec<Digest> = random_elements(leaf_count);
+        let archival_mmr: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_rustyleveldb_ammr_from_digests(leaf_digests.clone());
+
+        for leaf_index in 0..leaf_count as u64 {
+            let (membership_proof, _) = archival_mmr.prove_membership(leaf_index);
+            let compact = membership_proof.compact_authentication_path(leaf_count as u64);
+            assert_eq!(membership_proof.authentication_path, compact.digests());
+
+            // Re-derive the same left/right decisions `verify` makes, and check the packed
+            // bitmask agrees at every level.
+            let (mut mt_index, _) =
+                shared_basic::leaf_index_to_mt_index_and_peak_index(leaf_index, leaf_count as u64);
+            for level in 0..membership_proof.authentication_path.len() {
+                let node_is_right_child = mt_index % 2 == 1;
+                assert_eq!(node_is_right_child, compact.is_left_sibling(level));
+                mt_index /= 2;
+            }
+        }
+    }
+
+    #[test]
+    fn try_verify_agrees_with_verify_and_reports_the_specific_failure() {
+        type H = blake3::Hasher;
+
+        let leaf_count = 8;
+        let leaf_digests: Vec<Digest> = random_elements(leaf_count);
+        let archival_mmr: ArchivalMmr<H, RustyLevelDbVec<Digest>> =
+            get_rustyleveldb_ammr_from_digests(leaf_digests.clone());
+        let peaks = archival_mmr.get_peaks();
+
+        let leaf_index = 3;
+        let (membership_proof, _) = archival_mmr.prove_membership(leaf_index as u64);
+        let leaf_hash = leaf_digests[leaf_index];
+
+        let (verified, acc_hash) = membership_proof.verify(&peaks, &leaf_hash, leaf_count as u64);
+        assert!(verified);
+        assert_eq!(
+            acc_hash.unwrap(),
+            membership_proof
+                .try_verify(&peaks, &leaf_hash, leaf_count as u64)
+                .unwrap()
+        );
+
+        let mut too_short = membership_proof.clone();
+        too_short.authentication_path.pop();
+        assert_eq!(
+            Err(MmrError::AuthenticationPathLengthMismatch),
+            too_short.try_verify(&peaks, &leaf_hash, leaf_count as u64)
+        );
+
+        let wrong_leaf_hash = random_elements(1)[0];
+        assert_eq!(
+            Err(MmrError::PeakMismatch),
+            membership_proof.try_verify(&peaks, &wrong_leaf_hash, leaf_count as u64)
+        );
+
+        assert_eq!(
+            Err(MmrError::PeakIndexOutOfBounds),
+            membership_proof.try_verify(&[], &leaf_hash, leaf_count as u64)
+        );
+    }
+
     #[test]
     fn equality_and_hash_test() {
         type H = blake3::Hasher;
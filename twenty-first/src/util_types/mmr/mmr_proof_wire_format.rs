@@ -0,0 +1,126 @@
+//! An explicit, versioned wire format for [`MmrMembershipProof`] and [`MmrAccumulator`]. Both
+//! types already implement [`BFieldCodec`] directly, but that encoding carries no indication of
+//! which crate version produced it: a light client and an archival node built against different
+//! `twenty-first` versions could silently misinterpret each other's bytes if either type's field
+//! layout ever changes. [`Versioned`] prefixes a single format-version tag ahead of the wrapped
+//! value's own encoding and rejects, at decode time, any version it doesn't recognize.
+
+use anyhow::bail;
+use anyhow::Result;
+
+use crate::shared_math::b_field_element::BFieldElement;
+use crate::shared_math::bfield_codec::BFieldCodec;
+
+/// The only wire format version this crate currently knows how to decode. Bump this, and extend
+/// [`Versioned::decode`] to branch on the old value(s), the next time the wrapped types' encoding
+/// changes in an incompatible way.
+pub const CURRENT_MMR_PROOF_FORMAT_VERSION: u32 = 1;
+
+/// Wraps `T` with a leading format-version tag for encoding, decoding, and comparing versioned
+/// copies of the same underlying value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Versioned<T> {
+    format_version: u32,
+    pub value: T,
+}
+
+impl<T> Versioned<T> {
+    /// Wrap `value`, tagging it with [`CURRENT_MMR_PROOF_FORMAT_VERSION`].
+    pub fn new(value: T) -> Self {
+        Self {
+            format_version: CURRENT_MMR_PROOF_FORMAT_VERSION,
+            value,
+        }
+    }
+
+    pub fn format_version(&self) -> u32 {
+        self.format_version
+    }
+}
+
+impl<T: BFieldCodec> BFieldCodec for Versioned<T> {
+    fn decode(sequence: &[BFieldElement]) -> Result<Box<Self>> {
+        if sequence.is_empty() {
+            bail!("Cannot decode empty sequence as a versioned MMR proof: missing format-version tag.");
+        }
+
+        let format_version = *u32::decode(&sequence[0..1])?;
+        if format_version != CURRENT_MMR_PROOF_FORMAT_VERSION {
+            bail!(
+                "Unsupported MMR proof wire format version {format_version}; this crate only decodes version {CURRENT_MMR_PROOF_FORMAT_VERSION}."
+            );
+        }
+
+        let value = *T::decode(&sequence[1..])?;
+        Ok(Box::new(Self {
+            format_version,
+            value,
+        }))
+    }
+
+    fn encode(&self) -> Vec<BFieldElement> {
+        [self.format_version.encode(), self.value.encode()].concat()
+    }
+
+    fn static_length() -> Option<usize> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod versioned_mmr_proof_tests {
+    use crate::shared_math::digest::Digest;
+    use crate::shared_math::other::random_elements;
+    use crate::util_types::mmr::mmr_accumulator::MmrAccumulator;
+    use crate::util_types::mmr::mmr_membership_proof::MmrMembershipProof;
+
+    use super::*;
+
+    #[test]
+    fn versioned_membership_proof_round_trips() {
+        type H = blake3::Hasher;
+
+        let path: Vec<Digest> = random_elements(4);
+        let proof = MmrMembershipProof::<H>::new(7, path);
+        let versioned = Versioned::new(proof.clone());
+
+        let encoded = versioned.encode();
+        let decoded = *Versioned::<MmrMembershipProof<H>>::decode(&encoded).unwrap();
+
+        assert_eq!(CURRENT_MMR_PROOF_FORMAT_VERSION, decoded.format_version());
+        assert_eq!(proof, decoded.value);
+    }
+
+    #[test]
+    fn versioned_accumulator_round_trips() {
+        use crate::shared_math::tip5::Tip5;
+        type H = Tip5;
+
+        let leaves: Vec<Digest> = random_elements(16);
+        let mmra = MmrAccumulator::<H>::new(leaves);
+        let versioned = Versioned::new(mmra.clone());
+
+        let encoded = versioned.encode();
+        let decoded = *Versioned::<MmrAccumulator<H>>::decode(&encoded).unwrap();
+
+        assert_eq!(mmra, decoded.value);
+    }
+
+    #[test]
+    fn unrecognized_format_version_is_rejected() {
+        type H = blake3::Hasher;
+
+        let proof = MmrMembershipProof::<H>::new(0, vec![]);
+        let versioned = Versioned::new(proof);
+        let mut encoded = versioned.encode();
+        encoded[0] = BFieldElement::new(u64::from(CURRENT_MMR_PROOF_FORMAT_VERSION) + 1);
+
+        assert!(Versioned::<MmrMembershipProof<H>>::decode(&encoded).is_err());
+    }
+
+    #[test]
+    fn empty_sequence_is_rejected() {
+        type H = blake3::Hasher;
+        assert!(Versioned::<MmrMembershipProof<H>>::decode(&[]).is_err());
+    }
+}
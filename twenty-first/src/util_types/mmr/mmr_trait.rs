@@ -3,6 +3,30 @@ use crate::util_types::algebraic_hasher::AlgebraicHasher;
 
 use super::{mmr_accumulator::MmrAccumulator, mmr_membership_proof::MmrMembershipProof};
 
+/// Failure modes for verifying an [`MmrMembershipProof`], reported by
+/// [`MmrMembershipProof::try_verify`] instead of the plain `false` that
+/// [`MmrMembershipProof::verify`] returns for any of these conditions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MmrError {
+    /// The authentication path's length doesn't match the height implied by the leaf's position
+    /// in the MMR, so it can't possibly lead to the peak it's checked against.
+    AuthenticationPathLengthMismatch,
+    /// The leaf's position implies a peak index that doesn't exist among the given peaks, e.g.
+    /// because `leaf_count` doesn't match the MMR the peaks were taken from.
+    PeakIndexOutOfBounds,
+    /// The authentication path is the right length and leads to a peak, but that peak doesn't
+    /// match the expected one.
+    PeakMismatch,
+}
+
+impl std::fmt::Display for MmrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl std::error::Error for MmrError {}
+
 pub trait Mmr<H: AlgebraicHasher> {
     /// Create a new MMR instanc from a list of hash digests. The supplied digests
     /// are the leaves of the MMR.
@@ -34,6 +58,13 @@ pub trait Mmr<H: AlgebraicHasher> {
 
     /// Batch mutate an MMR while updating a list of membership proofs. Returns the indices of the
     /// membership proofs that have changed as a result of this operation.
+    ///
+    /// This exists so that a party tracking many membership proofs (e.g. a wallet tracking its
+    /// own UTXOs) can apply a batch of leaf mutations in one call. Every membership proof in
+    /// `membership_proofs` is patched using only the digests derivable from `mutation_data` and
+    /// its own authentication path, so the whole batch costs one pass over the mutations and one
+    /// pass over the membership proofs, rather than re-deriving each membership proof from
+    /// scratch for every individual mutation.
     fn batch_mutate_leaf_and_update_mps(
         &mut self,
         membership_proofs: &mut [&mut MmrMembershipProof<H>],
@@ -0,0 +1,118 @@
+//! Public, hardened re-exports of the MMR leaf/node index math in [`super::shared_advanced`], for
+//! external code that needs to convert between leaf and node indices, or compute a peak
+//! decomposition, without re-deriving (and potentially overflowing) the bit arithmetic itself.
+//!
+//! Every function here takes and returns leaf counts and indices as `u128`, wider than the `u64`
+//! that [`ArchivalMmr`](super::archival_mmr::ArchivalMmr) and
+//! [`MmrAccumulator`](super::mmr_accumulator::MmrAccumulator) actually store internally, so that a
+//! caller doing this arithmetic ahead of time (e.g. sizing a batch, or reasoning about a
+//! hypothetical future leaf count) is not tied to `u64`'s range while doing so. Each function
+//! returns `None`, rather than silently wrapping or panicking, if any input does not fit in a
+//! `u64` — the ceiling this crate's MMR implementations actually support.
+
+use super::shared_advanced;
+
+/// Convert a leaf index to the corresponding node index. See
+/// [`shared_advanced::leaf_index_to_node_index`].
+pub fn leaf_index_to_node_index(leaf_index: u128) -> Option<u128> {
+    let leaf_index: u64 = leaf_index.try_into().ok()?;
+    Some(shared_advanced::leaf_index_to_node_index(leaf_index).into())
+}
+
+/// The inverse of [`leaf_index_to_node_index`]: `None` if `node_index` is not a leaf. See
+/// [`shared_advanced::node_index_to_leaf_index`].
+pub fn node_index_to_leaf_index(node_index: u128) -> Option<u128> {
+    let node_index: u64 = node_index.try_into().ok()?;
+    shared_advanced::node_index_to_leaf_index(node_index).map(u128::from)
+}
+
+/// The node indices added to an MMR's flat storage by appending one leaf when it already has
+/// `old_leaf_count` leaves. See [`shared_advanced::node_indices_added_by_append`].
+pub fn node_indices_added_by_append(old_leaf_count: u128) -> Option<Vec<u128>> {
+    let old_leaf_count: u64 = old_leaf_count.try_into().ok()?;
+    Some(
+        shared_advanced::node_indices_added_by_append(old_leaf_count)
+            .into_iter()
+            .map(u128::from)
+            .collect(),
+    )
+}
+
+/// The heights and node indices of the peaks of an MMR with `leaf_count` leaves, i.e. its peak
+/// decomposition. See [`shared_advanced::get_peak_heights_and_peak_node_indices`].
+pub fn get_peak_heights_and_peak_node_indices(leaf_count: u128) -> Option<(Vec<u32>, Vec<u128>)> {
+    let leaf_count: u64 = leaf_count.try_into().ok()?;
+    let (heights, node_indices) =
+        shared_advanced::get_peak_heights_and_peak_node_indices(leaf_count);
+    Some((heights, node_indices.into_iter().map(u128::from).collect()))
+}
+
+/// The total number of nodes (leaves and internal) in an MMR with `leaf_count` leaves. See
+/// [`shared_advanced::leaf_count_to_node_count`].
+pub fn leaf_count_to_node_count(leaf_count: u128) -> Option<u128> {
+    let leaf_count: u64 = leaf_count.try_into().ok()?;
+    Some(shared_advanced::leaf_count_to_node_count(leaf_count).into())
+}
+
+#[cfg(test)]
+mod shared_index_math_tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_u64_implementation_for_in_range_values() {
+        for leaf_count in 0..200u64 {
+            assert_eq!(
+                Some((
+                    shared_advanced::get_peak_heights_and_peak_node_indices(leaf_count).0,
+                    shared_advanced::get_peak_heights_and_peak_node_indices(leaf_count)
+                        .1
+                        .into_iter()
+                        .map(u128::from)
+                        .collect::<Vec<_>>(),
+                )),
+                get_peak_heights_and_peak_node_indices(leaf_count as u128),
+            );
+            assert_eq!(
+                Some(shared_advanced::leaf_count_to_node_count(leaf_count) as u128),
+                leaf_count_to_node_count(leaf_count as u128),
+            );
+            assert_eq!(
+                Some(
+                    shared_advanced::node_indices_added_by_append(leaf_count)
+                        .into_iter()
+                        .map(u128::from)
+                        .collect::<Vec<_>>()
+                ),
+                node_indices_added_by_append(leaf_count as u128),
+            );
+
+            for leaf_index in 0..leaf_count {
+                let node_index = shared_advanced::leaf_index_to_node_index(leaf_index);
+                assert_eq!(
+                    Some(node_index as u128),
+                    leaf_index_to_node_index(leaf_index as u128),
+                );
+                assert_eq!(
+                    Some(leaf_index as u128),
+                    node_index_to_leaf_index(node_index as u128),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn out_of_u64_range_values_are_rejected_instead_of_overflowing() {
+        let too_big = u64::MAX as u128 + 1;
+        assert_eq!(None, leaf_index_to_node_index(too_big));
+        assert_eq!(None, node_index_to_leaf_index(too_big));
+        assert_eq!(None, node_indices_added_by_append(too_big));
+        assert_eq!(None, get_peak_heights_and_peak_node_indices(too_big));
+        assert_eq!(None, leaf_count_to_node_count(too_big));
+    }
+
+    #[test]
+    fn non_leaf_node_index_has_no_leaf_index() {
+        // Node index 3 is an internal node (parent of leaves 0 and 1), not a leaf.
+        assert_eq!(None, node_index_to_leaf_index(3));
+    }
+}
@@ -0,0 +1,171 @@
+//! A [`MerkleTree`] variant for workloads that mutate a handful of leaves between every
+//! commitment. Plain [`MerkleTree`] has no leaf-mutation method at all — updating even a single
+//! leaf means rebuilding the whole tree via [`CpuParallel::from_digests`]. [`MutableMerkleTree`]
+//! instead tracks which leaves changed since the last [`Self::root`] call and, when the root is
+//! next requested, recomputes only the ancestors on those leaves' paths, leaving every unaffected
+//! subtree root untouched.
+
+use std::collections::HashSet;
+
+use crate::shared_math::digest::Digest;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
+use crate::util_types::merkle_tree::CpuParallel;
+use crate::util_types::merkle_tree::MerkleTree;
+use crate::util_types::merkle_tree_maker::MerkleTreeMaker;
+
+pub struct MutableMerkleTree<H>
+where
+    H: AlgebraicHasher,
+{
+    inner: MerkleTree<H>,
+    /// Leaf indices changed since the last [`Self::root`] call.
+    dirty_leaves: HashSet<usize>,
+}
+
+impl<H> MutableMerkleTree<H>
+where
+    H: AlgebraicHasher,
+{
+    pub fn new(leaves: Vec<Digest>) -> Self {
+        let inner: MerkleTree<H> = CpuParallel::from_digests(&leaves);
+        Self {
+            inner,
+            dirty_leaves: HashSet::new(),
+        }
+    }
+
+    pub fn get_leaf_count(&self) -> usize {
+        self.inner.get_leaf_count()
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.inner.get_height()
+    }
+
+    /// The leaf's current digest, reflecting any [`Self::set_leaf`] calls made since construction
+    /// or the last [`Self::root`] call — leaf reads never need a recomputed root.
+    pub fn get_leaf_by_index(&self, leaf_index: usize) -> Digest {
+        self.inner.get_leaf_by_index(leaf_index)
+    }
+
+    /// Overwrite a leaf's digest and mark it dirty. Ancestor nodes are left stale until the next
+    /// [`Self::root`] call.
+    pub fn set_leaf(&mut self, leaf_index: usize, new_digest: Digest) {
+        let node_index = self.inner.get_leaf_count() + leaf_index;
+        self.inner.set_node(node_index, new_digest);
+        self.dirty_leaves.insert(leaf_index);
+    }
+
+    /// True if some leaf has been [`Self::set_leaf`]-updated since the last [`Self::root`] call.
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty_leaves.is_empty()
+    }
+
+    /// The tree's current root, recomputing only the ancestors of leaves dirtied since the last
+    /// call: every dirty leaf's parent is recomputed once, then that parent's parent, and so on up
+    /// to the root, deduplicating shared ancestors along the way. Subtrees with no dirty leaf
+    /// underneath them are never touched.
+    pub fn root(&mut self) -> Digest {
+        let leaf_count = self.inner.get_leaf_count();
+        let mut frontier: HashSet<usize> = self
+            .dirty_leaves
+            .drain()
+            .map(|leaf_index| leaf_index + leaf_count)
+            .collect();
+
+        while !frontier.is_empty() && !frontier.contains(&1) {
+            let mut parents = HashSet::new();
+            for node_index in frontier {
+                let parent_index = node_index / 2;
+                if parents.insert(parent_index) {
+                    let left = self.inner.node_at(parent_index * 2);
+                    let right = self.inner.node_at(parent_index * 2 + 1);
+                    self.inner
+                        .set_node(parent_index, H::hash_pair(&left, &right));
+                }
+            }
+            frontier = parents;
+        }
+
+        self.inner.get_root()
+    }
+}
+
+#[cfg(test)]
+mod mutable_merkle_tree_tests {
+    use rand::thread_rng;
+    use rand::Rng;
+
+    use crate::shared_math::other::random_elements;
+    use crate::shared_math::tip5::Tip5;
+
+    use super::*;
+
+    #[test]
+    fn root_matches_a_freshly_built_tree_after_random_updates() {
+        type H = Tip5;
+
+        let num_leaves = 16;
+        let mut leaves: Vec<Digest> = random_elements(num_leaves);
+        let mut tree = MutableMerkleTree::<H>::new(leaves.clone());
+        assert!(!tree.is_dirty());
+
+        let mut rng = thread_rng();
+        for _ in 0..20 {
+            let num_updates = rng.gen_range(1..=5);
+            for _ in 0..num_updates {
+                let leaf_index = rng.gen_range(0..num_leaves);
+                let new_digest = random_elements::<Digest>(1)[0];
+                leaves[leaf_index] = new_digest;
+                tree.set_leaf(leaf_index, new_digest);
+            }
+            assert!(tree.is_dirty());
+
+            let expected: MerkleTree<H> = CpuParallel::from_digests(&leaves);
+            assert_eq!(expected.get_root(), tree.root());
+            assert!(!tree.is_dirty());
+        }
+    }
+
+    #[test]
+    fn untouched_leaves_are_unaffected_by_unrelated_updates() {
+        type H = Tip5;
+
+        let leaves: Vec<Digest> = random_elements(8);
+        let mut tree = MutableMerkleTree::<H>::new(leaves);
+
+        let untouched_leaf_index = 5;
+        let untouched_digest = tree.get_leaf_by_index(untouched_leaf_index);
+
+        tree.set_leaf(0, random_elements::<Digest>(1)[0]);
+        tree.root();
+
+        assert_eq!(
+            untouched_digest,
+            tree.get_leaf_by_index(untouched_leaf_index)
+        );
+    }
+
+    #[test]
+    fn root_of_a_freshly_built_tree_matches_with_no_updates_at_all() {
+        type H = Tip5;
+
+        let leaves: Vec<Digest> = random_elements(8);
+        let expected: MerkleTree<H> = CpuParallel::from_digests(&leaves);
+        let mut tree = MutableMerkleTree::<H>::new(leaves);
+
+        assert_eq!(expected.get_root(), tree.root());
+    }
+
+    #[test]
+    fn calling_root_twice_without_updates_is_idempotent() {
+        type H = Tip5;
+
+        let leaves: Vec<Digest> = random_elements(8);
+        let mut tree = MutableMerkleTree::<H>::new(leaves);
+
+        let root_once = tree.root();
+        let root_twice = tree.root();
+        assert_eq!(root_once, root_twice);
+    }
+}
@@ -0,0 +1,246 @@
+//! A generalization of [`MerkleTree`] to configurable arity: instead of every internal node
+//! having exactly two children, it has `ARITY` of them. A wider tree is shallower, so
+//! authentication paths are shorter, and each level's hash absorbs `ARITY` digests in one call
+//! to [`AlgebraicHasher::hash_varlen`] instead of `ARITY - 1` nested calls to
+//! [`AlgebraicHasher::hash_pair`] — useful when `ARITY` is chosen to match a sponge's rate, so a
+//! level's children fill (close to) a whole absorption.
+//!
+//! This lives alongside [`MerkleTree`] rather than replacing it: [`MerkleTree`] has the shape of
+//! `ARITY = 2` but hashes each pair with `hash_pair`, so its roots differ from those of an
+//! `ARITY = 2` tree here. The rest of the crate (and
+//! [`MerkleTreeMaker`](crate::util_types::merkle_tree_maker::MerkleTreeMaker) implementors) is
+//! built around that `hash_pair` layout, so existing binary trees keep using it unchanged.
+
+use std::marker::PhantomData;
+
+use anyhow::bail;
+use anyhow::Result;
+use itertools::Itertools;
+
+use crate::shared_math::digest::Digest;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
+
+/// A Merkle tree in which every internal node has `ARITY` children, stored level-order with the
+/// root at index `0` and the children of node `i` at indices `i * ARITY + 1 ..= i * ARITY + ARITY`.
+#[derive(Debug, Clone)]
+pub struct NAryMerkleTree<H, const ARITY: usize>
+where
+    H: AlgebraicHasher,
+{
+    nodes: Vec<Digest>,
+    num_leaves: usize,
+    _hasher: PhantomData<H>,
+}
+
+impl<H, const ARITY: usize> NAryMerkleTree<H, ARITY>
+where
+    H: AlgebraicHasher,
+{
+    /// Hash `children` into their parent digest. Uses [`AlgebraicHasher::hash_varlen`] rather
+    /// than a fixed-arity fast path, since `ARITY` is a compile-time choice of the caller and not
+    /// generally the width the underlying permutation is optimized for.
+    fn hash_children(children: &[Digest]) -> Digest {
+        let sequence = children
+            .iter()
+            .flat_map(|digest| digest.values())
+            .collect_vec();
+        H::hash_varlen(&sequence)
+    }
+
+    /// Build a tree from `leaves`. `leaves.len()` must be a power of `ARITY`, and `ARITY` must be
+    /// at least `2`.
+    pub fn from_digests(leaves: &[Digest]) -> Result<Self> {
+        if ARITY < 2 {
+            bail!("Arity must be at least 2, got {ARITY}.");
+        }
+        if leaves.is_empty() {
+            bail!("Cannot build a Merkle tree from zero leaves.");
+        }
+
+        let mut num_leaves_at_height = leaves.len();
+        let mut height = 0;
+        while num_leaves_at_height > 1 {
+            if !num_leaves_at_height.is_multiple_of(ARITY) {
+                bail!("Number of leaves must be a power of the arity ({ARITY}).");
+            }
+            num_leaves_at_height /= ARITY;
+            height += 1;
+        }
+
+        let num_nodes = (0..=height)
+            .map(|h| leaves.len() / ARITY.pow(h as u32))
+            .sum();
+        let mut nodes = vec![Digest::default(); num_nodes];
+
+        let first_leaf_index = num_nodes - leaves.len();
+        nodes[first_leaf_index..].copy_from_slice(leaves);
+
+        let mut level_start = first_leaf_index;
+        let mut level_len = leaves.len();
+        while level_len > 1 {
+            let parent_level_len = level_len / ARITY;
+            let parent_level_start = level_start - parent_level_len;
+            for parent_offset in 0..parent_level_len {
+                let children_start = level_start + parent_offset * ARITY;
+                let parent = Self::hash_children(&nodes[children_start..children_start + ARITY]);
+                nodes[parent_level_start + parent_offset] = parent;
+            }
+            level_start = parent_level_start;
+            level_len = parent_level_len;
+        }
+
+        Ok(Self {
+            nodes,
+            num_leaves: leaves.len(),
+            _hasher: PhantomData,
+        })
+    }
+
+    pub fn root(&self) -> Digest {
+        self.nodes[0]
+    }
+
+    pub fn num_leaves(&self) -> usize {
+        self.num_leaves
+    }
+
+    pub fn leaf(&self, leaf_index: usize) -> Digest {
+        self.nodes[self.nodes.len() - self.num_leaves + leaf_index]
+    }
+
+    /// The authentication path for `leaf_index`: for every level from the leaves up to (but not
+    /// including) the root, the `ARITY - 1` sibling digests of the node on the path, in
+    /// increasing order of their position among their siblings.
+    pub fn authentication_path(&self, leaf_index: usize) -> Vec<Vec<Digest>> {
+        assert!(leaf_index < self.num_leaves, "Leaf index out of bounds.");
+
+        let mut path = vec![];
+        let mut level_start = self.nodes.len() - self.num_leaves;
+        let mut level_len = self.num_leaves;
+        let mut index_in_level = leaf_index;
+
+        while level_len > 1 {
+            let group_start = index_in_level - index_in_level % ARITY;
+            let siblings = (0..ARITY)
+                .filter(|&i| group_start + i != index_in_level)
+                .map(|i| self.nodes[level_start + group_start + i])
+                .collect_vec();
+            path.push(siblings);
+
+            let parent_level_len = level_len / ARITY;
+            let parent_level_start = level_start - parent_level_len;
+            level_start = parent_level_start;
+            level_len = parent_level_len;
+            index_in_level /= ARITY;
+        }
+
+        path
+    }
+
+    /// Verify an authentication path produced by [`Self::authentication_path`] against
+    /// `expected_root`.
+    pub fn verify_authentication_path(
+        expected_root: Digest,
+        num_leaves: usize,
+        leaf_index: usize,
+        leaf: Digest,
+        authentication_path: &[Vec<Digest>],
+    ) -> bool {
+        if leaf_index >= num_leaves {
+            return false;
+        }
+
+        let mut running_digest = leaf;
+        let mut index_in_level = leaf_index;
+        for siblings in authentication_path {
+            if siblings.len() != ARITY - 1 {
+                return false;
+            }
+            let position_among_siblings = index_in_level % ARITY;
+            let mut children = siblings.clone();
+            children.insert(position_among_siblings, running_digest);
+            running_digest = Self::hash_children(&children);
+            index_in_level /= ARITY;
+        }
+
+        running_digest == expected_root
+    }
+}
+
+#[cfg(test)]
+mod n_ary_merkle_tree_tests {
+    use crate::shared_math::other::random_elements;
+    use crate::shared_math::tip5::Tip5;
+
+    use super::*;
+
+    fn random_leaves(num_leaves: usize) -> Vec<Digest> {
+        random_elements(num_leaves)
+    }
+
+    #[test]
+    fn quaternary_tree_round_trips_authentication_paths() {
+        let leaves = random_leaves(64);
+        let tree = NAryMerkleTree::<Tip5, 4>::from_digests(&leaves).unwrap();
+
+        for leaf_index in 0..leaves.len() {
+            let path = tree.authentication_path(leaf_index);
+            assert_eq!(3, path.len());
+            assert!(NAryMerkleTree::<Tip5, 4>::verify_authentication_path(
+                tree.root(),
+                leaves.len(),
+                leaf_index,
+                leaves[leaf_index],
+                &path,
+            ));
+        }
+    }
+
+    #[test]
+    fn octonary_tree_round_trips_authentication_paths() {
+        let leaves = random_leaves(8 * 8);
+        let tree = NAryMerkleTree::<Tip5, 8>::from_digests(&leaves).unwrap();
+
+        for leaf_index in [0, 1, 7, 8, 63] {
+            let path = tree.authentication_path(leaf_index);
+            assert!(NAryMerkleTree::<Tip5, 8>::verify_authentication_path(
+                tree.root(),
+                leaves.len(),
+                leaf_index,
+                leaves[leaf_index],
+                &path,
+            ));
+        }
+    }
+
+    #[test]
+    fn tampered_leaf_fails_verification() {
+        let leaves = random_leaves(16);
+        let tree = NAryMerkleTree::<Tip5, 4>::from_digests(&leaves).unwrap();
+        let path = tree.authentication_path(5);
+
+        let wrong_leaf = random_leaves(1)[0];
+        assert!(!NAryMerkleTree::<Tip5, 4>::verify_authentication_path(
+            tree.root(),
+            leaves.len(),
+            5,
+            wrong_leaf,
+            &path,
+        ));
+    }
+
+    #[test]
+    fn non_power_of_arity_leaf_count_is_rejected() {
+        let leaves = random_leaves(10);
+        assert!(NAryMerkleTree::<Tip5, 4>::from_digests(&leaves).is_err());
+    }
+
+    #[test]
+    fn single_leaf_tree_has_empty_authentication_path() {
+        let leaves = random_leaves(1);
+        let tree = NAryMerkleTree::<Tip5, 4>::from_digests(&leaves).unwrap();
+
+        assert_eq!(leaves[0], tree.root());
+        assert!(tree.authentication_path(0).is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+//! A [`MerkleTree`](crate::util_types::merkle_tree::MerkleTree) variant whose node storage is
+//! pluggable, following the same `Storage: StorageVec<Digest>` pattern already used by
+//! [`ArchivalMmr`](crate::util_types::mmr::archival_mmr::ArchivalMmr): the caller supplies the
+//! backing store — an in-memory [`OrdinaryVec`](crate::util_types::storage_vec::OrdinaryVec), a
+//! [`RustyLevelDbVec`](crate::util_types::storage_vec::RustyLevelDbVec), or any other
+//! [`StorageVec`] implementation — instead of always committing to an in-memory `Vec<Digest>` the
+//! way [`MerkleTree`](crate::util_types::merkle_tree::MerkleTree) does. This makes it possible to
+//! build a Merkle tree whose nodes live in a memory-mapped file or a database, at the cost of the
+//! parallel construction and de-duplicated multiproofs `MerkleTree` offers.
+//!
+//! Nodes are stored in the same 1-indexed, heap-style layout as `MerkleTree`: the root is at index
+//! 1, and the children of node `i` are at `2 * i` and `2 * i + 1`.
+
+use std::marker::PhantomData;
+
+use crate::shared_math::digest::Digest;
+use crate::shared_math::other::is_power_of_two;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
+use crate::util_types::storage_vec::StorageVec;
+
+pub struct PluggableMerkleTree<H, Storage>
+where
+    H: AlgebraicHasher,
+    Storage: StorageVec<Digest>,
+{
+    nodes: Storage,
+    num_leaves: usize,
+    _hasher: PhantomData<H>,
+}
+
+impl<H, Storage> PluggableMerkleTree<H, Storage>
+where
+    H: AlgebraicHasher,
+    Storage: StorageVec<Digest>,
+{
+    pub fn get_root(&self) -> Digest {
+        self.nodes.get(1)
+    }
+
+    pub fn get_leaf_count(&self) -> usize {
+        self.num_leaves
+    }
+
+    pub fn get_leaf_by_index(&self, index: usize) -> Digest {
+        assert!(index < self.num_leaves, "Leaf index out of bounds.");
+        self.nodes.get((self.num_leaves + index) as u64)
+    }
+
+    /// The authentication path for `leaf_index`: one sibling digest per level, from the leaf up to
+    /// (but not including) the root.
+    pub fn authentication_path(&self, leaf_index: usize) -> Vec<Digest> {
+        assert!(leaf_index < self.num_leaves, "Leaf index out of bounds.");
+        let mut node_index = self.num_leaves + leaf_index;
+        let mut path = Vec::with_capacity(self.num_leaves.ilog2() as usize);
+        while node_index > 1 {
+            path.push(self.nodes.get((node_index ^ 1) as u64));
+            node_index /= 2;
+        }
+        path
+    }
+
+    /// Verify an authentication path against `expected_root`, without needing any
+    /// [`PluggableMerkleTree`] or its underlying storage at all.
+    pub fn verify_authentication_path(
+        expected_root: Digest,
+        tree_height: usize,
+        leaf_index: usize,
+        leaf: Digest,
+        authentication_path: &[Digest],
+    ) -> bool {
+        if leaf_index >= 1 << tree_height || authentication_path.len() != tree_height {
+            return false;
+        }
+
+        let mut node_index = (1 << tree_height) + leaf_index;
+        let mut node_digest = leaf;
+        for &sibling_digest in authentication_path {
+            node_digest = if node_index.is_multiple_of(2) {
+                H::hash_pair(&node_digest, &sibling_digest)
+            } else {
+                H::hash_pair(&sibling_digest, &node_digest)
+            };
+            node_index /= 2;
+        }
+
+        node_digest == expected_root
+    }
+}
+
+/// Analogous to [`MerkleTreeMaker`](crate::util_types::merkle_tree_maker::MerkleTreeMaker), but
+/// parameterized over the storage backend a [`PluggableMerkleTree`] is built into.
+pub trait PluggableMerkleTreeMaker<H, Storage>
+where
+    H: AlgebraicHasher,
+    Storage: StorageVec<Digest>,
+{
+    fn from_digests(digests: &[Digest], storage: Storage) -> PluggableMerkleTree<H, Storage>;
+}
+
+/// Builds a [`PluggableMerkleTree`] one node at a time, in the order the underlying `Storage`
+/// naturally supports (`push` followed by `set`), with no assumption that the storage backend can
+/// be indexed or written to in parallel — unlike
+/// [`CpuParallel`](crate::util_types::merkle_tree::CpuParallel), which relies on `Vec<Digest>`
+/// supporting exactly that.
+#[derive(Debug)]
+pub struct Sequential;
+
+impl<H, Storage> PluggableMerkleTreeMaker<H, Storage> for Sequential
+where
+    H: AlgebraicHasher,
+    Storage: StorageVec<Digest>,
+{
+    fn from_digests(digests: &[Digest], mut storage: Storage) -> PluggableMerkleTree<H, Storage> {
+        let num_leaves = digests.len();
+        assert!(
+            is_power_of_two(num_leaves),
+            "Size of input for Merkle tree must be a power of 2"
+        );
+        assert!(storage.is_empty(), "Storage must be empty.");
+
+        // Node 0 is never used; it is populated with a filler so that indices line up with the
+        // usual 1-indexed, heap-style layout.
+        for _ in 0..2 * num_leaves {
+            storage.push(digests[0]);
+        }
+        for (i, &leaf) in digests.iter().enumerate() {
+            storage.set((num_leaves + i) as u64, leaf);
+        }
+        for i in (1..num_leaves).rev() {
+            let left = storage.get((2 * i) as u64);
+            let right = storage.get((2 * i + 1) as u64);
+            storage.set(i as u64, H::hash_pair(&left, &right));
+        }
+
+        PluggableMerkleTree {
+            nodes: storage,
+            num_leaves,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod pluggable_merkle_tree_tests {
+    use crate::shared_math::other::random_elements;
+    use crate::shared_math::tip5::Tip5;
+    use crate::util_types::merkle_tree::CpuParallel;
+    use crate::util_types::merkle_tree::MerkleTree;
+    use crate::util_types::merkle_tree_maker::MerkleTreeMaker;
+    use crate::util_types::storage_vec::OrdinaryVec;
+
+    use super::*;
+
+    #[test]
+    fn ordinary_vec_backed_tree_matches_the_in_memory_merkle_tree() {
+        let leaves: Vec<Digest> = random_elements(16);
+
+        let regular: MerkleTree<Tip5> = CpuParallel::from_digests(&leaves);
+        let pluggable: PluggableMerkleTree<Tip5, OrdinaryVec<Digest>> =
+            Sequential::from_digests(&leaves, OrdinaryVec::default());
+
+        assert_eq!(regular.get_root(), pluggable.get_root());
+        for i in 0..leaves.len() {
+            assert_eq!(regular.get_leaf_by_index(i), pluggable.get_leaf_by_index(i));
+        }
+    }
+
+    #[test]
+    fn authentication_path_round_trips_through_verification() {
+        let leaves: Vec<Digest> = random_elements(8);
+        let tree: PluggableMerkleTree<Tip5, OrdinaryVec<Digest>> =
+            Sequential::from_digests(&leaves, OrdinaryVec::default());
+
+        for leaf_index in 0..leaves.len() {
+            let path = tree.authentication_path(leaf_index);
+            assert!(
+                PluggableMerkleTree::<Tip5, OrdinaryVec<Digest>>::verify_authentication_path(
+                    tree.get_root(),
+                    3,
+                    leaf_index,
+                    leaves[leaf_index],
+                    &path,
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn tampered_leaf_fails_verification() {
+        let leaves: Vec<Digest> = random_elements(8);
+        let tree: PluggableMerkleTree<Tip5, OrdinaryVec<Digest>> =
+            Sequential::from_digests(&leaves, OrdinaryVec::default());
+
+        let path = tree.authentication_path(2);
+        let wrong_leaf: Digest = random_elements(1)[0];
+        assert!(
+            !PluggableMerkleTree::<Tip5, OrdinaryVec<Digest>>::verify_authentication_path(
+                tree.get_root(),
+                3,
+                2,
+                wrong_leaf,
+                &path,
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "must be a power of 2")]
+    fn non_power_of_two_leaf_count_is_rejected() {
+        let leaves: Vec<Digest> = random_elements(5);
+        let _: PluggableMerkleTree<Tip5, OrdinaryVec<Digest>> =
+            Sequential::from_digests(&leaves, OrdinaryVec::default());
+    }
+}
@@ -0,0 +1,101 @@
+use std::sync::{Arc, Mutex};
+
+use rusty_leveldb::{Status, WriteBatch, DB};
+
+/// A view onto a shared LevelDB instance whose keys are namespaced with a fixed prefix byte.
+/// Several independent persistent structures can each get their own `PrefixedDb` over the same
+/// `Arc<Mutex<DB>>`, so they end up sharing a single lock and a single compaction instead of each
+/// requiring its own physical database. [`super::storage_vec::RustyLevelDbVec`] already does its
+/// own ad hoc version of this (a `key_prefix: u8` field it prepends to every key by hand); this is
+/// the same idea factored out for reuse by other prefix-sharing structures.
+#[derive(Clone)]
+pub struct PrefixedDb {
+    db: Arc<Mutex<DB>>,
+    prefix: u8,
+}
+
+impl PrefixedDb {
+    pub fn new(db: Arc<Mutex<DB>>, prefix: u8) -> Self {
+        Self { db, prefix }
+    }
+
+    /// Prepend this instance's prefix to `key`, producing the raw key actually stored in the
+    /// underlying database. Exposed so callers assembling a [`WriteBatch`] across several keys
+    /// can namespace each key themselves before handing the batch to [`Self::write`].
+    pub fn namespace(&self, key: &[u8]) -> Vec<u8> {
+        let mut namespaced = Vec::with_capacity(key.len() + 1);
+        namespaced.push(self.prefix);
+        namespaced.extend_from_slice(key);
+        namespaced
+    }
+
+    pub fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.db.lock().unwrap().get(&self.namespace(key))
+    }
+
+    pub fn put(&self, key: &[u8], value: &[u8]) -> Result<(), Status> {
+        self.db.lock().unwrap().put(&self.namespace(key), value)
+    }
+
+    pub fn delete(&self, key: &[u8]) -> Result<(), Status> {
+        self.db.lock().unwrap().delete(&self.namespace(key))
+    }
+
+    /// Commit a batch of already-[`Self::namespace`]d writes in one call to the shared database.
+    pub fn write(&self, batch: WriteBatch, sync: bool) -> Result<(), Status> {
+        self.db.lock().unwrap().write(batch, sync)
+    }
+
+    pub fn flush(&self) -> Result<(), Status> {
+        self.db.lock().unwrap().flush()
+    }
+}
+
+#[cfg(test)]
+mod prefixed_db_tests {
+    use super::*;
+
+    fn shared_db() -> Arc<Mutex<DB>> {
+        let opt = rusty_leveldb::in_memory();
+        Arc::new(Mutex::new(DB::open("mydatabase", opt).unwrap()))
+    }
+
+    #[test]
+    fn different_prefixes_do_not_collide_on_the_same_key() {
+        let db = shared_db();
+        let a = PrefixedDb::new(db.clone(), 0);
+        let b = PrefixedDb::new(db, 1);
+
+        a.put(b"key", b"value from a").unwrap();
+        b.put(b"key", b"value from b").unwrap();
+
+        assert_eq!(Some(b"value from a".to_vec()), a.get(b"key"));
+        assert_eq!(Some(b"value from b".to_vec()), b.get(b"key"));
+    }
+
+    #[test]
+    fn get_delete_round_trip() {
+        let db = shared_db();
+        let prefixed = PrefixedDb::new(db, 7);
+
+        assert_eq!(None, prefixed.get(b"key"));
+        prefixed.put(b"key", b"value").unwrap();
+        assert_eq!(Some(b"value".to_vec()), prefixed.get(b"key"));
+        prefixed.delete(b"key").unwrap();
+        assert_eq!(None, prefixed.get(b"key"));
+    }
+
+    #[test]
+    fn write_batch_applies_namespaced_keys_atomically() {
+        let db = shared_db();
+        let prefixed = PrefixedDb::new(db, 3);
+
+        let mut batch = WriteBatch::new();
+        batch.put(&prefixed.namespace(b"x"), b"1");
+        batch.put(&prefixed.namespace(b"y"), b"2");
+        prefixed.write(batch, true).unwrap();
+
+        assert_eq!(Some(b"1".to_vec()), prefixed.get(b"x"));
+        assert_eq!(Some(b"2".to_vec()), prefixed.get(b"y"));
+    }
+}
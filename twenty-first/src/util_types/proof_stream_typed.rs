@@ -6,6 +6,13 @@ use std::marker::PhantomData;
 use crate::shared_math::{b_field_element::BFieldElement, digest::Digest};
 use crate::util_types::algebraic_hasher::AlgebraicHasher;
 
+/// A proof stream over a caller-supplied `Item` type, typically an enum of the concrete proof
+/// items a particular protocol exchanges (e.g. a Merkle root, an authentication structure, an
+/// out-of-domain evaluation). `Item` only needs to know how to flatten itself into
+/// [`BFieldElement`]s; [`Self::enqueue`] appends those elements to the running transcript
+/// immediately, so every item enqueued or dequeued is absorbed into the Fiat–Shamir transcript
+/// automatically, by construction, rather than by prover and verifier separately remembering to
+/// hash it.
 #[derive(Debug, PartialEq, Eq)]
 pub struct ProofStream<Item, H: AlgebraicHasher> {
     items: Vec<(Item, usize)>,
@@ -245,4 +252,47 @@ mod proof_stream_typed_tests {
             "prover_fiat_shamir() and verifier_fiat_shamir() are equivalent when the entire stream is read again",
         );
     }
+
+    // A protocol-shaped item enum, standing in for the kind of typed proof items (Merkle roots,
+    // authentication structures, ...) a real protocol would enqueue.
+    #[derive(Clone, Debug, PartialEq)]
+    enum ProtocolItem {
+        Commitment(Digest),
+        Opening(Vec<BFieldElement>),
+    }
+
+    impl IntoIterator for ProtocolItem {
+        type Item = BFieldElement;
+        type IntoIter = std::vec::IntoIter<BFieldElement>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            match self {
+                ProtocolItem::Commitment(digest) => digest.values().to_vec().into_iter(),
+                ProtocolItem::Opening(bs) => bs.into_iter(),
+            }
+        }
+    }
+
+    #[test]
+    fn enqueueing_a_typed_item_absorbs_it_into_the_transcript_without_a_separate_hash_step() {
+        type H = Tip5;
+        let mut proof_stream = ProofStream::<ProtocolItem, H>::default();
+
+        let before = proof_stream.transcript_length();
+        let commitment = H::hash(&BFieldElement::one());
+        proof_stream.enqueue(&ProtocolItem::Commitment(commitment));
+
+        // No explicit "absorb this into the sponge" call is needed: enqueueing already extended
+        // the transcript, so prover_fiat_shamir() reflects the item right away.
+        assert!(proof_stream.transcript_length() > before);
+        assert_eq!(proof_stream.transcript_length(), commitment.values().len(),);
+        let fiat_shamir_after_enqueue = proof_stream.prover_fiat_shamir();
+
+        proof_stream.enqueue(&ProtocolItem::Opening(vec![BFieldElement::one()]));
+        assert_ne!(
+            fiat_shamir_after_enqueue,
+            proof_stream.prover_fiat_shamir(),
+            "a second enqueued item must change the transcript's hash"
+        );
+    }
 }
@@ -0,0 +1,254 @@
+//! A sponge wrapper implementing (a simplified form of) the SAFE calling convention ("Sponge API
+//! for Field Elements"): callers declare the whole sequence of absorptions and squeezes up
+//! front as an [`IOPattern`], the pattern is bound into the sponge's state before any data is
+//! processed, and every subsequent [`SafeSponge::absorb`]/[`SafeSponge::squeeze`] call is
+//! checked against that declaration.
+//!
+//! Binding the IO pattern up front is what makes transcripts interoperable across SAFE-compliant
+//! proof systems: two implementations that agree on the pattern and the underlying permutation
+//! produce byte-identical transcripts, without needing to agree on any other framing convention.
+
+use std::collections::VecDeque;
+
+use itertools::Itertools;
+
+use crate::shared_math::b_field_element::{BFieldElement, BFIELD_ONE, BFIELD_ZERO};
+use crate::util_types::algebraic_hasher::{SpongeHasher, RATE};
+
+/// One element of an [`IOPattern`]: an absorption or squeeze of a given number of field
+/// elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IOPatternElement {
+    Absorb(usize),
+    Squeeze(usize),
+}
+
+/// A declaration of the whole sequence of absorptions and squeezes a [`SafeSponge`] session will
+/// perform, used to domain-separate the session from any other use of the same permutation.
+///
+/// Consecutive calls to [`IOPattern::absorb`] (or [`IOPattern::squeeze`]) are merged into a
+/// single pattern element, matching the SAFE convention that only the *shape* of alternation
+/// between absorbing and squeezing matters, not how a caller happened to chunk it.
+#[derive(Debug, Clone, Default)]
+pub struct IOPattern(Vec<IOPatternElement>);
+
+impl IOPattern {
+    pub fn new() -> Self {
+        Self(vec![])
+    }
+
+    pub fn absorb(mut self, num_elements: usize) -> Self {
+        match self.0.last_mut() {
+            Some(IOPatternElement::Absorb(n)) => *n += num_elements,
+            _ => self.0.push(IOPatternElement::Absorb(num_elements)),
+        }
+        self
+    }
+
+    pub fn squeeze(mut self, num_elements: usize) -> Self {
+        match self.0.last_mut() {
+            Some(IOPatternElement::Squeeze(n)) => *n += num_elements,
+            _ => self.0.push(IOPatternElement::Squeeze(num_elements)),
+        }
+        self
+    }
+
+    /// Encode the pattern as a sequence of field elements suitable for absorbing as a
+    /// domain-separation tag: each element is `(is_squeeze, count)`.
+    fn encode(&self) -> Vec<BFieldElement> {
+        self.0
+            .iter()
+            .flat_map(|op| match op {
+                IOPatternElement::Absorb(n) => [BFIELD_ZERO, BFieldElement::new(*n as u64)],
+                IOPatternElement::Squeeze(n) => [BFIELD_ONE, BFieldElement::new(*n as u64)],
+            })
+            .collect()
+    }
+}
+
+/// A sponge session following the SAFE calling convention: [`SafeSponge::start`], any number of
+/// [`SafeSponge::absorb`]/[`SafeSponge::squeeze`] calls matching the declared [`IOPattern`], and
+/// finally [`SafeSponge::finish`].
+pub struct SafeSponge<H: SpongeHasher> {
+    sponge: H::SpongeState,
+    pattern: VecDeque<IOPatternElement>,
+    absorb_buffer: Vec<BFieldElement>,
+    squeeze_buffer: VecDeque<BFieldElement>,
+}
+
+impl<H: SpongeHasher> SafeSponge<H> {
+    /// Start a new SAFE session: bind `pattern` into the sponge's state before any real data is
+    /// absorbed, so that two sessions with different declared patterns never collide even if
+    /// they later absorb the same bytes.
+    pub fn start(pattern: IOPattern) -> Self {
+        let mut sponge = H::init();
+        let tag = pattern.encode();
+        H::absorb_repeatedly(&mut sponge, pad_to_rate(&tag).iter());
+
+        Self {
+            sponge,
+            pattern: pattern.0.into(),
+            absorb_buffer: vec![],
+            squeeze_buffer: VecDeque::new(),
+        }
+    }
+
+    /// Advance to the next declared pattern element once the current one is fully consumed.
+    fn expect_absorb(&mut self, num_elements: usize) {
+        match self.pattern.front_mut() {
+            Some(IOPatternElement::Absorb(remaining)) if *remaining >= num_elements => {
+                *remaining -= num_elements;
+                if *remaining == 0 {
+                    self.pattern.pop_front();
+                }
+            }
+            _ => panic!(
+                "SAFE sponge misuse: absorb({num_elements}) does not match the declared IO pattern"
+            ),
+        }
+    }
+
+    fn expect_squeeze(&mut self, num_elements: usize) {
+        match self.pattern.front_mut() {
+            Some(IOPatternElement::Squeeze(remaining)) if *remaining >= num_elements => {
+                *remaining -= num_elements;
+                if *remaining == 0 {
+                    self.pattern.pop_front();
+                }
+            }
+            _ => panic!(
+                "SAFE sponge misuse: squeeze({num_elements}) does not match the declared IO pattern"
+            ),
+        }
+    }
+
+    /// Absorb `input`, which must match the next (or continuing) declared absorption.
+    pub fn absorb(&mut self, input: &[BFieldElement]) {
+        self.expect_absorb(input.len());
+        self.squeeze_buffer.clear();
+
+        self.absorb_buffer.extend_from_slice(input);
+        while self.absorb_buffer.len() >= RATE {
+            let block: [BFieldElement; RATE] = self.absorb_buffer[..RATE].try_into().unwrap();
+            H::absorb(&mut self.sponge, &block);
+            self.absorb_buffer.drain(..RATE);
+        }
+    }
+
+    /// Squeeze `num_elements` field elements, which must match the next (or continuing) declared
+    /// squeeze.
+    pub fn squeeze(&mut self, num_elements: usize) -> Vec<BFieldElement> {
+        self.expect_squeeze(num_elements);
+
+        if !self.absorb_buffer.is_empty() {
+            let padded = pad_to_rate(&std::mem::take(&mut self.absorb_buffer));
+            let block: [BFieldElement; RATE] = padded[..RATE].try_into().unwrap();
+            H::absorb(&mut self.sponge, &block);
+        }
+
+        while self.squeeze_buffer.len() < num_elements {
+            self.squeeze_buffer.extend(H::squeeze(&mut self.sponge));
+        }
+
+        (0..num_elements)
+            .map(|_| self.squeeze_buffer.pop_front().unwrap())
+            .collect_vec()
+    }
+
+    /// Finish the session. Panics if the declared [`IOPattern`] was not fully consumed, since
+    /// that indicates a protocol implementation bug rather than something a caller should
+    /// silently ignore.
+    pub fn finish(self) {
+        assert!(
+            self.pattern.is_empty(),
+            "SAFE sponge misuse: IO pattern was not fully consumed"
+        );
+    }
+}
+
+fn pad_to_rate(elements: &[BFieldElement]) -> Vec<BFieldElement> {
+    let mut padded = elements.to_vec();
+    padded.push(BFIELD_ONE);
+    padded.resize(padded.len().next_multiple_of(RATE).max(RATE), BFIELD_ZERO);
+    padded
+}
+
+#[cfg(test)]
+mod safe_sponge_tests {
+    use crate::shared_math::other::random_elements;
+    use crate::shared_math::tip5::Tip5;
+
+    use super::*;
+
+    #[test]
+    fn matching_pattern_round_trips() {
+        let pattern = IOPattern::new().absorb(3).squeeze(2).absorb(4).squeeze(1);
+        let mut sponge = SafeSponge::<Tip5>::start(pattern);
+
+        sponge.absorb(&random_elements(3));
+        let _ = sponge.squeeze(2);
+        sponge.absorb(&random_elements(4));
+        let _ = sponge.squeeze(1);
+        sponge.finish();
+    }
+
+    #[test]
+    fn split_absorb_matching_merged_pattern_round_trips() {
+        // Two absorbs in a row are merged by IOPattern, so splitting one logical absorb into
+        // two calls is fine as long as the total matches.
+        let pattern = IOPattern::new().absorb(5).squeeze(1);
+        let mut sponge = SafeSponge::<Tip5>::start(pattern);
+
+        sponge.absorb(&random_elements(2));
+        sponge.absorb(&random_elements(3));
+        let _ = sponge.squeeze(1);
+        sponge.finish();
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match the declared IO pattern")]
+    fn absorbing_more_than_declared_panics() {
+        let pattern = IOPattern::new().absorb(2);
+        let mut sponge = SafeSponge::<Tip5>::start(pattern);
+        sponge.absorb(&random_elements(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "IO pattern was not fully consumed")]
+    fn finishing_early_panics() {
+        let pattern = IOPattern::new().absorb(2).squeeze(1);
+        let mut sponge = SafeSponge::<Tip5>::start(pattern);
+        sponge.absorb(&random_elements(2));
+        sponge.finish();
+    }
+
+    #[test]
+    fn same_pattern_and_input_gives_same_output() {
+        let input = random_elements(3);
+
+        let mut a = SafeSponge::<Tip5>::start(IOPattern::new().absorb(3).squeeze(2));
+        a.absorb(&input);
+        let out_a = a.squeeze(2);
+
+        let mut b = SafeSponge::<Tip5>::start(IOPattern::new().absorb(3).squeeze(2));
+        b.absorb(&input);
+        let out_b = b.squeeze(2);
+
+        assert_eq!(out_a, out_b);
+    }
+
+    #[test]
+    fn different_pattern_gives_different_output() {
+        let input = random_elements(3);
+
+        let mut a = SafeSponge::<Tip5>::start(IOPattern::new().absorb(3).squeeze(2));
+        a.absorb(&input);
+        let out_a = a.squeeze(2);
+
+        let mut b = SafeSponge::<Tip5>::start(IOPattern::new().absorb(3).squeeze(5));
+        b.absorb(&input);
+        let out_b = b.squeeze(2);
+
+        assert_ne!(out_a, out_b);
+    }
+}
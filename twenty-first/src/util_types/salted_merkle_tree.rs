@@ -0,0 +1,234 @@
+//! A [`MerkleTree`] variant in which every leaf is committed to together with a prover-chosen
+//! salt, so that opening a leaf during spot-checking does not reveal anything about neighbouring,
+//! unopened leaves beyond what the (padded) codeword length already implies. This is the
+//! zero-knowledge counterpart to a plain [`MerkleTree`]: without salts, revealing enough leaves of
+//! a low-degree codeword can leak information about the codeword itself, since the committed value
+//! *is* the leaf.
+//!
+//! The salts are managed entirely by the tree: [`SaltedMerkleTree::from_digests`] draws one salt
+//! per leaf, and an opened leaf's salt is revealed alongside it as part of a
+//! [`SaltedAuthenticationStructure`], exactly the extra piece of information a verifier needs to
+//! recompute the same committed digest and check it against the authentication structure of the
+//! underlying, salt-committed [`MerkleTree`].
+
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
+
+use crate::shared_math::digest::Digest;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
+use crate::util_types::merkle_tree::AuthenticationStructure;
+use crate::util_types::merkle_tree::CpuParallel;
+use crate::util_types::merkle_tree::MerkleTree;
+use crate::util_types::merkle_tree_maker::MerkleTreeMaker;
+
+/// The data a verifier needs to check that a set of leaves, together with their prover-chosen
+/// salts, are the ones committed to by a [`SaltedMerkleTree`]'s root: the salts themselves, plus
+/// the authentication structure of the underlying salted-leaf commitments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaltedAuthenticationStructure {
+    pub salts: Vec<Digest>,
+    pub authentication_structure: AuthenticationStructure,
+}
+
+#[derive(Debug, Clone)]
+pub struct SaltedMerkleTree<H>
+where
+    H: AlgebraicHasher,
+{
+    leaves: Vec<Digest>,
+    salts: Vec<Digest>,
+    inner: MerkleTree<H>,
+}
+
+impl<H> SaltedMerkleTree<H>
+where
+    H: AlgebraicHasher,
+{
+    /// Commit to `leaves`, salting each one with a fresh, uniformly random [`Digest`].
+    pub fn from_digests(leaves: &[Digest]) -> Self {
+        let mut rng = StdRng::from_entropy();
+        let salts = (0..leaves.len()).map(|_| rng.gen()).collect();
+        Self::from_digests_and_salts(leaves, salts)
+    }
+
+    /// Commit to `leaves`, salting each one with the corresponding, caller-chosen `salts`. Useful
+    /// for deterministic tests and for re-deriving a tree whose salts were persisted separately.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `leaves` and `salts` do not have the same length.
+    pub fn from_digests_and_salts(leaves: &[Digest], salts: Vec<Digest>) -> Self {
+        assert_eq!(
+            leaves.len(),
+            salts.len(),
+            "Every leaf must be salted with exactly one salt."
+        );
+
+        let committed_leaves = leaves
+            .iter()
+            .zip(&salts)
+            .map(|(leaf, salt)| H::hash_pair(leaf, salt))
+            .collect::<Vec<_>>();
+        let inner: MerkleTree<H> = CpuParallel::from_digests(&committed_leaves);
+
+        Self {
+            leaves: leaves.to_vec(),
+            salts,
+            inner,
+        }
+    }
+
+    pub fn get_root(&self) -> Digest {
+        self.inner.get_root()
+    }
+
+    pub fn get_leaf_count(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.inner.get_height()
+    }
+
+    pub fn get_leaf_by_index(&self, index: usize) -> Digest {
+        self.leaves[index]
+    }
+
+    pub fn get_salt_by_index(&self, index: usize) -> Digest {
+        self.salts[index]
+    }
+
+    /// The authentication structure for `leaf_indices`, together with the salts of exactly those
+    /// leaves, revealing nothing about the salts of any other leaf.
+    pub fn authentication_structure(
+        &self,
+        leaf_indices: &[usize],
+    ) -> SaltedAuthenticationStructure {
+        let salts = leaf_indices.iter().map(|&i| self.salts[i]).collect();
+        let authentication_structure = self.inner.get_authentication_structure(leaf_indices);
+        SaltedAuthenticationStructure {
+            salts,
+            authentication_structure,
+        }
+    }
+
+    /// Verify a [`SaltedAuthenticationStructure`] against `expected_root`: the claimed `leaves`
+    /// are re-salted with their revealed salts and checked against the authentication structure
+    /// of the underlying, salt-committed [`MerkleTree`].
+    pub fn verify_authentication_structure(
+        expected_root: Digest,
+        tree_height: usize,
+        leaf_indices: &[usize],
+        leaves: &[Digest],
+        salted_authentication_structure: &SaltedAuthenticationStructure,
+    ) -> bool {
+        if leaves.len() != salted_authentication_structure.salts.len() {
+            return false;
+        }
+
+        let committed_leaves = leaves
+            .iter()
+            .zip(&salted_authentication_structure.salts)
+            .map(|(leaf, salt)| H::hash_pair(leaf, salt))
+            .collect::<Vec<_>>();
+
+        MerkleTree::<H>::verify_authentication_structure(
+            expected_root,
+            tree_height,
+            leaf_indices,
+            &committed_leaves,
+            &salted_authentication_structure.authentication_structure,
+        )
+    }
+}
+
+#[cfg(test)]
+mod salted_merkle_tree_tests {
+    use crate::shared_math::other::random_elements;
+    use crate::shared_math::tip5::Tip5;
+    use crate::test_shared::corrupt_digest;
+
+    use super::*;
+
+    #[test]
+    fn same_leaves_with_different_salts_have_different_roots() {
+        let leaves: Vec<Digest> = random_elements(8);
+        let tree_a = SaltedMerkleTree::<Tip5>::from_digests(&leaves);
+        let tree_b = SaltedMerkleTree::<Tip5>::from_digests(&leaves);
+
+        assert_ne!(tree_a.get_root(), tree_b.get_root());
+    }
+
+    #[test]
+    fn honestly_revealed_leaves_and_salts_verify() {
+        let leaves: Vec<Digest> = random_elements(8);
+        let tree = SaltedMerkleTree::<Tip5>::from_digests(&leaves);
+
+        let opened_indices = [1, 4, 6];
+        let opened_leaves = opened_indices
+            .iter()
+            .map(|&i| leaves[i])
+            .collect::<Vec<_>>();
+        let salted_auth_structure = tree.authentication_structure(&opened_indices);
+
+        assert!(SaltedMerkleTree::<Tip5>::verify_authentication_structure(
+            tree.get_root(),
+            tree.get_height(),
+            &opened_indices,
+            &opened_leaves,
+            &salted_auth_structure,
+        ));
+    }
+
+    #[test]
+    fn wrong_salt_fails_verification() {
+        let leaves: Vec<Digest> = random_elements(8);
+        let tree = SaltedMerkleTree::<Tip5>::from_digests(&leaves);
+
+        let opened_indices = [2];
+        let opened_leaves = opened_indices
+            .iter()
+            .map(|&i| leaves[i])
+            .collect::<Vec<_>>();
+        let mut salted_auth_structure = tree.authentication_structure(&opened_indices);
+        salted_auth_structure.salts[0] = corrupt_digest(&salted_auth_structure.salts[0]);
+
+        assert!(!SaltedMerkleTree::<Tip5>::verify_authentication_structure(
+            tree.get_root(),
+            tree.get_height(),
+            &opened_indices,
+            &opened_leaves,
+            &salted_auth_structure,
+        ));
+    }
+
+    #[test]
+    fn wrong_leaf_fails_verification() {
+        let leaves: Vec<Digest> = random_elements(8);
+        let tree = SaltedMerkleTree::<Tip5>::from_digests(&leaves);
+
+        let opened_indices = [3];
+        let wrong_leaves: Vec<Digest> = random_elements(1);
+        let salted_auth_structure = tree.authentication_structure(&opened_indices);
+
+        assert!(!SaltedMerkleTree::<Tip5>::verify_authentication_structure(
+            tree.get_root(),
+            tree.get_height(),
+            &opened_indices,
+            &wrong_leaves,
+            &salted_auth_structure,
+        ));
+    }
+
+    #[test]
+    fn deterministic_salts_reproduce_the_same_tree() {
+        let leaves: Vec<Digest> = random_elements(4);
+        let salts: Vec<Digest> = random_elements(4);
+
+        let tree_a = SaltedMerkleTree::<Tip5>::from_digests_and_salts(&leaves, salts.clone());
+        let tree_b = SaltedMerkleTree::<Tip5>::from_digests_and_salts(&leaves, salts);
+
+        assert_eq!(tree_a.get_root(), tree_b.get_root());
+    }
+}
@@ -0,0 +1,134 @@
+use std::sync::{Arc, Mutex};
+
+use super::database_vector::{DatabaseVector, DatabaseVectorError};
+use super::database_vector_codec::{BincodeCodec, ValueCodec};
+
+type IndexType = u64;
+
+/// A cloneable, `Send + Sync` handle to a [`DatabaseVector`], obtained via
+/// [`DatabaseVector::into_shared`].
+///
+/// Every [`DatabaseVector`] method takes `&mut self`, even reads like `get` and `len`, because the
+/// underlying `rusty_leveldb::DB` itself requires `&mut self` for reads. So there is no getting
+/// concurrent readers out of this: every method here takes the same lock, meaning callers on
+/// different threads are still fully serialized against each other. What this buys is safe
+/// sharing of a single `DatabaseVector` across threads at all, e.g. handing clones to worker
+/// threads that occasionally touch the same persistent vector, instead of needing one `DatabaseVector`
+/// per thread with no way to reconcile them.
+pub struct SharedDatabaseVector<T, C: ValueCodec<T> = BincodeCodec> {
+    inner: Arc<Mutex<DatabaseVector<T, C>>>,
+}
+
+// Implemented by hand instead of derived: `#[derive(Clone)]` would require `T: Clone` and
+// `C: Clone`, but cloning only ever bumps the `Arc`'s reference count.
+impl<T, C: ValueCodec<T>> Clone for SharedDatabaseVector<T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: Clone, C: ValueCodec<T>> SharedDatabaseVector<T, C> {
+    pub fn new(database_vector: DatabaseVector<T, C>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(database_vector)),
+        }
+    }
+
+    pub fn len(&self) -> IndexType {
+        self.inner.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.lock().unwrap().is_empty()
+    }
+
+    pub fn try_get(&self, index: IndexType) -> Result<T, DatabaseVectorError> {
+        self.inner.lock().unwrap().try_get(index)
+    }
+
+    pub fn get(&self, index: IndexType) -> T {
+        self.inner.lock().unwrap().get(index)
+    }
+
+    pub fn try_set(&self, index: IndexType, value: T) -> Result<(), DatabaseVectorError> {
+        self.inner.lock().unwrap().try_set(index, value)
+    }
+
+    pub fn set(&self, index: IndexType, value: T) {
+        self.inner.lock().unwrap().set(index, value)
+    }
+
+    pub fn push(&self, value: T) {
+        self.inner.lock().unwrap().push(value)
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        self.inner.lock().unwrap().pop()
+    }
+
+    pub fn persist(&self) {
+        self.inner.lock().unwrap().persist()
+    }
+}
+
+impl<T: Clone, C: ValueCodec<T>> DatabaseVector<T, C> {
+    /// Wrap this vector in a cloneable, thread-safe [`SharedDatabaseVector`] handle.
+    pub fn into_shared(self) -> SharedDatabaseVector<T, C> {
+        SharedDatabaseVector::new(self)
+    }
+}
+
+#[cfg(test)]
+mod shared_database_vector_tests {
+    use super::*;
+    use rusty_leveldb::DB;
+    use std::thread;
+
+    fn test_shared_vector() -> SharedDatabaseVector<u64> {
+        let opt = rusty_leveldb::in_memory();
+        let db = DB::open("mydatabase", opt).unwrap();
+        DatabaseVector::new(db).into_shared()
+    }
+
+    fn assert_send_and_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn shared_database_vector_is_send_and_sync() {
+        assert_send_and_sync::<SharedDatabaseVector<u64>>();
+    }
+
+    #[test]
+    fn a_clone_sees_writes_made_through_another_clone() {
+        let handle_a = test_shared_vector();
+        let handle_b = handle_a.clone();
+
+        handle_a.push(17);
+        assert_eq!(1, handle_b.len());
+        assert_eq!(17, handle_b.get(0));
+
+        handle_b.set(0, 42);
+        assert_eq!(42, handle_a.get(0));
+    }
+
+    #[test]
+    fn clones_shared_across_threads_agree_on_the_final_length() {
+        let handle = test_shared_vector();
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let handle = handle.clone();
+                thread::spawn(move || {
+                    for _ in 0..25 {
+                        handle.push(1);
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(200, handle.len());
+    }
+}
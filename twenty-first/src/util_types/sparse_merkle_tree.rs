@@ -0,0 +1,250 @@
+//! A sparse Merkle tree of a fixed `depth`, keyed by `u64` indices in `0..2^depth`. Unlike
+//! [`MerkleTree`](crate::util_types::merkle_tree::MerkleTree), which materializes every leaf,
+//! a [`SparseMerkleTree`] only stores the leaves that have been explicitly [`set`][Self::set] and
+//! their ancestors; every other leaf implicitly holds a fixed `default_leaf` digest. This makes it
+//! suitable for key-value commitments over a large key space where only a handful of keys are
+//! ever populated. `depth` is at most 63: leaf indices are `u64` and are checked against
+//! `1 << depth`.
+//!
+//! Non-membership of a key follows from the same authentication path machinery as membership:
+//! to prove key `k` is unset, present [`Self::authentication_path`] for `k` together with the
+//! `default_leaf` itself as the claimed leaf digest, and verify as usual.
+
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use crate::shared_math::digest::Digest;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
+
+/// The digest of an empty subtree of each height, from the leaves (height `0`) up to the root
+/// (height `depth`), derived once from `default_leaf` and reused by every empty position.
+fn default_digests<H: AlgebraicHasher>(depth: usize, default_leaf: Digest) -> Vec<Digest> {
+    let mut digests = Vec::with_capacity(depth + 1);
+    digests.push(default_leaf);
+    for height in 0..depth {
+        let child = digests[height];
+        digests.push(H::hash_pair(&child, &child));
+    }
+    digests
+}
+
+#[derive(Debug, Clone)]
+pub struct SparseMerkleTree<H>
+where
+    H: AlgebraicHasher,
+{
+    depth: usize,
+    default_leaf: Digest,
+    default_digests: Vec<Digest>,
+
+    /// Only the nodes on the path of a [`set`][Self::set] leaf are stored, keyed by
+    /// `(height, index at that height)`; every other node holds the default digest of its height.
+    nodes: HashMap<(usize, u64), Digest>,
+    _hasher: PhantomData<H>,
+}
+
+impl<H> SparseMerkleTree<H>
+where
+    H: AlgebraicHasher,
+{
+    /// Build an empty tree of the given `depth`, i.e., `2^depth` addressable leaves, all
+    /// implicitly holding `default_leaf`.
+    pub fn new(depth: usize, default_leaf: Digest) -> Self {
+        Self {
+            depth,
+            default_leaf,
+            default_digests: default_digests::<H>(depth, default_leaf),
+            nodes: HashMap::new(),
+            _hasher: PhantomData,
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn default_leaf(&self) -> Digest {
+        self.default_leaf
+    }
+
+    fn digest_at(&self, height: usize, index: u64) -> Digest {
+        self.nodes
+            .get(&(height, index))
+            .copied()
+            .unwrap_or(self.default_digests[height])
+    }
+
+    /// The digest stored at `index`, or `default_leaf` if `index` was never [`set`][Self::set].
+    pub fn get(&self, index: u64) -> Digest {
+        self.digest_at(0, index)
+    }
+
+    /// Set the leaf at `index` to `leaf`, updating every node on the path to the root.
+    pub fn set(&mut self, index: u64, leaf: Digest) {
+        assert!(
+            index < 1 << self.depth,
+            "Leaf index {index} out of range for a tree of depth {}.",
+            self.depth
+        );
+
+        self.nodes.insert((0, index), leaf);
+
+        let mut node_index = index;
+        let mut node_digest = leaf;
+        for height in 0..self.depth {
+            let sibling_index = node_index ^ 1;
+            let sibling_digest = self.digest_at(height, sibling_index);
+
+            node_digest = if node_index.is_multiple_of(2) {
+                H::hash_pair(&node_digest, &sibling_digest)
+            } else {
+                H::hash_pair(&sibling_digest, &node_digest)
+            };
+            node_index /= 2;
+            self.nodes.insert((height + 1, node_index), node_digest);
+        }
+    }
+
+    pub fn root(&self) -> Digest {
+        self.digest_at(self.depth, 0)
+    }
+
+    /// The authentication path for `index`: one sibling digest per height, from the leaves up to
+    /// (but not including) the root.
+    pub fn authentication_path(&self, index: u64) -> Vec<Digest> {
+        assert!(
+            index < 1 << self.depth,
+            "Leaf index {index} out of range for a tree of depth {}.",
+            self.depth
+        );
+
+        let mut node_index = index;
+        let mut path = Vec::with_capacity(self.depth);
+        for height in 0..self.depth {
+            path.push(self.digest_at(height, node_index ^ 1));
+            node_index /= 2;
+        }
+        path
+    }
+
+    /// Verify an authentication path against `expected_root`. Used both for membership (`leaf`
+    /// is the claimed value at `index`) and non-membership (`leaf` is the tree's `default_leaf`)
+    /// proofs; the two differ only in which digest is passed as `leaf`.
+    pub fn verify_authentication_path(
+        expected_root: Digest,
+        depth: usize,
+        index: u64,
+        leaf: Digest,
+        authentication_path: &[Digest],
+    ) -> bool {
+        if index >= 1 << depth || authentication_path.len() != depth {
+            return false;
+        }
+
+        let mut node_index = index;
+        let mut node_digest = leaf;
+        for &sibling_digest in authentication_path {
+            node_digest = if node_index.is_multiple_of(2) {
+                H::hash_pair(&node_digest, &sibling_digest)
+            } else {
+                H::hash_pair(&sibling_digest, &node_digest)
+            };
+            node_index /= 2;
+        }
+
+        node_digest == expected_root
+    }
+}
+
+#[cfg(test)]
+mod sparse_merkle_tree_tests {
+    use crate::shared_math::other::random_elements;
+    use crate::shared_math::tip5::Tip5;
+
+    use super::*;
+
+    #[test]
+    fn empty_tree_root_matches_default_digest_of_full_height() {
+        let default_leaf = Digest::default();
+        let tree = SparseMerkleTree::<Tip5>::new(4, default_leaf);
+        assert_eq!(tree.default_digests[4], tree.root());
+    }
+
+    #[test]
+    fn unset_leaf_reads_back_as_default_and_proves_as_non_member() {
+        let default_leaf = Digest::default();
+        let tree = SparseMerkleTree::<Tip5>::new(10, default_leaf);
+
+        let index = 123;
+        assert_eq!(default_leaf, tree.get(index));
+
+        let path = tree.authentication_path(index);
+        assert!(SparseMerkleTree::<Tip5>::verify_authentication_path(
+            tree.root(),
+            10,
+            index,
+            default_leaf,
+            &path,
+        ));
+    }
+
+    #[test]
+    fn set_leaf_round_trips_membership_proof() {
+        let default_leaf = Digest::default();
+        let mut tree = SparseMerkleTree::<Tip5>::new(10, default_leaf);
+
+        let index = 987;
+        let value: Digest = random_elements(1)[0];
+        tree.set(index, value);
+
+        assert_eq!(value, tree.get(index));
+        let path = tree.authentication_path(index);
+        assert!(SparseMerkleTree::<Tip5>::verify_authentication_path(
+            tree.root(),
+            10,
+            index,
+            value,
+            &path,
+        ));
+
+        // Every other key is still unset and still proves as a non-member.
+        let other_index = 988;
+        let other_path = tree.authentication_path(other_index);
+        assert!(SparseMerkleTree::<Tip5>::verify_authentication_path(
+            tree.root(),
+            10,
+            other_index,
+            default_leaf,
+            &other_path,
+        ));
+    }
+
+    #[test]
+    fn wrong_leaf_fails_verification() {
+        let default_leaf = Digest::default();
+        let mut tree = SparseMerkleTree::<Tip5>::new(8, default_leaf);
+        let value: Digest = random_elements(1)[0];
+        tree.set(42, value);
+
+        let path = tree.authentication_path(42);
+        let wrong_value: Digest = random_elements(1)[0];
+        assert!(!SparseMerkleTree::<Tip5>::verify_authentication_path(
+            tree.root(),
+            8,
+            42,
+            wrong_value,
+            &path,
+        ));
+    }
+
+    #[test]
+    fn overwriting_a_leaf_updates_the_root() {
+        let default_leaf = Digest::default();
+        let mut tree = SparseMerkleTree::<Tip5>::new(6, default_leaf);
+        tree.set(5, random_elements(1)[0]);
+        let first_root = tree.root();
+
+        tree.set(5, random_elements(1)[0]);
+        assert_ne!(first_root, tree.root());
+    }
+}
@@ -0,0 +1,130 @@
+//! A deterministic pseudorandom number generator built on a [`SpongeHasher`], so that protocol
+//! randomness (index sampling, weights, and the like) can be derived reproducibly from a single
+//! audited primitive instead of an ad-hoc construction.
+
+use std::collections::VecDeque;
+
+use rand::RngCore;
+
+use crate::shared_math::b_field_element::BFIELD_ZERO;
+use crate::shared_math::digest::{Digest, DIGEST_LENGTH};
+use crate::util_types::algebraic_hasher::{SpongeHasher, RATE};
+
+/// A [`RngCore`] implementation that derives all of its output from repeatedly squeezing a
+/// sponge seeded with a [`Digest`].
+///
+/// Two `SpongePrng`s seeded with the same digest produce the exact same stream of output,
+/// regardless of platform, making protocol randomness reproducible from a transcript.
+#[derive(Debug, Clone)]
+pub struct SpongePrng<H: SpongeHasher> {
+    sponge: H::SpongeState,
+    buffer: VecDeque<u64>,
+}
+
+impl<H: SpongeHasher> SpongePrng<H> {
+    /// Seed a new `SpongePrng` from a [`Digest`].
+    pub fn from_seed(seed: Digest) -> Self {
+        let mut sponge = H::init();
+        let mut input = [BFIELD_ZERO; RATE];
+        input[..DIGEST_LENGTH].copy_from_slice(&seed.values());
+        H::absorb(&mut sponge, &input);
+
+        Self {
+            sponge,
+            buffer: VecDeque::new(),
+        }
+    }
+
+    /// Squeeze the sponge once more, buffering its output words.
+    fn refill(&mut self) {
+        let squeezed = H::squeeze(&mut self.sponge);
+        self.buffer.extend(squeezed.iter().map(|e| e.value()));
+    }
+
+    /// Produce the next 64-bit word, squeezing the sponge if the buffer is empty.
+    fn next_word(&mut self) -> u64 {
+        if self.buffer.is_empty() {
+            self.refill();
+        }
+        self.buffer.pop_front().unwrap()
+    }
+}
+
+impl<H: SpongeHasher> RngCore for SpongePrng<H> {
+    fn next_u32(&mut self) -> u32 {
+        self.next_word() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.next_word()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let word = self.next_word().to_le_bytes();
+            chunk.copy_from_slice(&word[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod sponge_prng_tests {
+    use itertools::Itertools;
+
+    use crate::shared_math::b_field_element::BFieldElement;
+    use crate::shared_math::digest::Digest;
+    use crate::shared_math::other::random_elements;
+    use crate::shared_math::tip5::Tip5;
+
+    use super::*;
+
+    fn random_seed() -> Digest {
+        Digest::new(
+            random_elements::<BFieldElement>(DIGEST_LENGTH)
+                .try_into()
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn same_seed_gives_same_stream() {
+        let seed = random_seed();
+        let mut a = SpongePrng::<Tip5>::from_seed(seed);
+        let mut b = SpongePrng::<Tip5>::from_seed(seed);
+
+        let words_a = (0..50).map(|_| a.next_u64()).collect_vec();
+        let words_b = (0..50).map(|_| b.next_u64()).collect_vec();
+
+        assert_eq!(words_a, words_b);
+    }
+
+    #[test]
+    fn different_seeds_give_different_streams() {
+        let mut a = SpongePrng::<Tip5>::from_seed(random_seed());
+        let mut b = SpongePrng::<Tip5>::from_seed(random_seed());
+
+        let words_a = (0..10).map(|_| a.next_u64()).collect_vec();
+        let words_b = (0..10).map(|_| b.next_u64()).collect_vec();
+
+        assert_ne!(words_a, words_b);
+    }
+
+    #[test]
+    fn fill_bytes_is_deterministic() {
+        let seed = random_seed();
+        let mut a = SpongePrng::<Tip5>::from_seed(seed);
+        let mut b = SpongePrng::<Tip5>::from_seed(seed);
+
+        let mut bytes_a = [0u8; 37];
+        let mut bytes_b = [0u8; 37];
+        a.fill_bytes(&mut bytes_a);
+        b.fill_bytes(&mut bytes_b);
+
+        assert_eq!(bytes_a, bytes_b);
+    }
+}
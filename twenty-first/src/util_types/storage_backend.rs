@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+
+use rusty_leveldb::{LdbIterator, WriteBatch, DB};
+
+/// A single write to apply as part of a [`StorageBackend::write_batch`] call.
+pub enum BatchOperation {
+    Put(Vec<u8>, Vec<u8>),
+    Delete(Vec<u8>),
+}
+
+/// Abstraction over the key-value store backing persistent structures such as
+/// [`super::database_vector::DatabaseVector`], so those structures aren't hard-wired to one
+/// storage crate.
+///
+/// [`LevelDbBackend`] wraps `rusty-leveldb`, the crate used everywhere else in this module.
+/// [`InMemoryBackend`] is a dependency-free backend useful for tests. Backends for other
+/// key-value stores (e.g. sled, RocksDB) are a natural fit behind Cargo feature flags, but adding
+/// one means bringing in a new dependency, which is out of scope here — this only lays the trait
+/// down so a future backend needs no changes to the structures built on top of it.
+pub trait StorageBackend {
+    fn get(&mut self, key: &[u8]) -> Option<Vec<u8>>;
+    fn put(&mut self, key: &[u8], value: &[u8]);
+    fn delete(&mut self, key: &[u8]);
+    fn write_batch(&mut self, operations: Vec<BatchOperation>);
+
+    /// All entries currently in the store. No ordering is guaranteed across backends.
+    fn iterate(&mut self) -> Vec<(Vec<u8>, Vec<u8>)>;
+}
+
+pub struct LevelDbBackend {
+    db: DB,
+}
+
+impl LevelDbBackend {
+    pub fn new(db: DB) -> Self {
+        Self { db }
+    }
+}
+
+impl StorageBackend for LevelDbBackend {
+    fn get(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        self.db.get(key)
+    }
+
+    fn put(&mut self, key: &[u8], value: &[u8]) {
+        self.db.put(key, value).expect("LevelDB put must succeed");
+    }
+
+    fn delete(&mut self, key: &[u8]) {
+        self.db.delete(key).expect("LevelDB delete must succeed");
+    }
+
+    fn write_batch(&mut self, operations: Vec<BatchOperation>) {
+        let mut batch = WriteBatch::new();
+        for operation in operations {
+            match operation {
+                BatchOperation::Put(key, value) => batch.put(&key, &value),
+                BatchOperation::Delete(key) => batch.delete(&key),
+            }
+        }
+        self.db
+            .write(batch, true)
+            .expect("LevelDB batch write must succeed");
+    }
+
+    fn iterate(&mut self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let mut db_iterator = self
+            .db
+            .new_iter()
+            .expect("Opening a LevelDB iterator must succeed");
+        let mut entries = Vec::new();
+        while let Some(entry) = db_iterator.next() {
+            entries.push(entry);
+        }
+        entries
+    }
+}
+
+/// A dependency-free [`StorageBackend`] backed by an in-memory `BTreeMap`. Useful for tests that
+/// want a real `StorageBackend` without paying for a LevelDB instance.
+#[derive(Default)]
+pub struct InMemoryBackend {
+    entries: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl InMemoryBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StorageBackend for InMemoryBackend {
+    fn get(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        self.entries.get(key).cloned()
+    }
+
+    fn put(&mut self, key: &[u8], value: &[u8]) {
+        self.entries.insert(key.to_vec(), value.to_vec());
+    }
+
+    fn delete(&mut self, key: &[u8]) {
+        self.entries.remove(key);
+    }
+
+    fn write_batch(&mut self, operations: Vec<BatchOperation>) {
+        for operation in operations {
+            match operation {
+                BatchOperation::Put(key, value) => {
+                    self.entries.insert(key, value);
+                }
+                BatchOperation::Delete(key) => {
+                    self.entries.remove(&key);
+                }
+            }
+        }
+    }
+
+    fn iterate(&mut self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        self.entries
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod storage_backend_tests {
+    use super::*;
+
+    fn exercise(mut backend: impl StorageBackend) {
+        assert_eq!(None, backend.get(b"a"));
+
+        backend.put(b"a", b"1");
+        backend.put(b"b", b"2");
+        assert_eq!(Some(b"1".to_vec()), backend.get(b"a"));
+
+        backend.write_batch(vec![
+            BatchOperation::Put(b"c".to_vec(), b"3".to_vec()),
+            BatchOperation::Delete(b"a".to_vec()),
+        ]);
+        assert_eq!(None, backend.get(b"a"));
+        assert_eq!(Some(b"3".to_vec()), backend.get(b"c"));
+
+        let mut entries = backend.iterate();
+        entries.sort();
+        assert_eq!(
+            vec![
+                (b"b".to_vec(), b"2".to_vec()),
+                (b"c".to_vec(), b"3".to_vec())
+            ],
+            entries
+        );
+
+        backend.delete(b"b");
+        assert_eq!(None, backend.get(b"b"));
+    }
+
+    #[test]
+    fn level_db_backend_behaves_like_the_trait_contract() {
+        let opt = rusty_leveldb::in_memory();
+        let db = DB::open("mydatabase", opt).unwrap();
+        exercise(LevelDbBackend::new(db));
+    }
+
+    #[test]
+    fn in_memory_backend_behaves_like_the_trait_contract() {
+        exercise(InMemoryBackend::new());
+    }
+}
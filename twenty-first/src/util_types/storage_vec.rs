@@ -208,6 +208,12 @@ impl<T: Serialize + DeserializeOwned> RustyLevelDbVec<T> {
 
 pub struct OrdinaryVec<T>(Vec<T>);
 
+impl<T> Default for OrdinaryVec<T> {
+    fn default() -> Self {
+        Self(vec![])
+    }
+}
+
 impl<T: Clone> StorageVec<T> for OrdinaryVec<T> {
     fn is_empty(&self) -> bool {
         self.0.is_empty()
@@ -0,0 +1,93 @@
+use super::storage_backend::{BatchOperation, StorageBackend};
+
+/// A set of writes collected from one or more persistent structures sharing a
+/// [`StorageBackend`], committed to the backend as a single [`StorageBackend::write_batch`] call.
+/// That call is only as atomic as the backend's `write_batch`: `LevelDbBackend` writes one LevelDB
+/// batch, while `MmapBackend` and `InMemoryBackend` apply the operations one after another.
+///
+/// This is what keeps, say, an archival MMR and its companion index vectors consistent across a
+/// crash: instead of each structure writing straight to the backend as it goes (leaving a window
+/// where the MMR has been updated but its index hasn't, or vice versa), every structure enqueues
+/// its pending writes here with [`Self::put`]/[`Self::delete`], and only [`Self::commit`] actually
+/// touches the backend, all at once. Dropping a `Transaction` without committing discards its
+/// queued writes, which is the whole rollback story: nothing was ever written.
+#[derive(Default)]
+pub struct Transaction {
+    operations: Vec<BatchOperation>,
+}
+
+impl Transaction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Queue a write of `value` under `key`.
+    pub fn put(&mut self, key: Vec<u8>, value: Vec<u8>) {
+        self.operations.push(BatchOperation::Put(key, value));
+    }
+
+    /// Queue a deletion of `key`.
+    pub fn delete(&mut self, key: Vec<u8>) {
+        self.operations.push(BatchOperation::Delete(key));
+    }
+
+    /// Apply every queued write to `backend` in one write batch.
+    pub fn commit(self, backend: &mut impl StorageBackend) {
+        backend.write_batch(self.operations);
+    }
+}
+
+#[cfg(test)]
+mod transaction_tests {
+    use super::*;
+    use crate::util_types::storage_backend::InMemoryBackend;
+
+    #[test]
+    fn commit_applies_every_queued_write_atomically() {
+        let mut backend = InMemoryBackend::new();
+        backend.put(b"stale", b"leftover");
+
+        let mut txn = Transaction::new();
+        txn.put(b"mmr_root".to_vec(), b"deadbeef".to_vec());
+        txn.put(b"index_len".to_vec(), b"1".to_vec());
+        txn.delete(b"stale".to_vec());
+        txn.commit(&mut backend);
+
+        assert_eq!(Some(b"deadbeef".to_vec()), backend.get(b"mmr_root"));
+        assert_eq!(Some(b"1".to_vec()), backend.get(b"index_len"));
+        assert_eq!(None, backend.get(b"stale"));
+    }
+
+    #[test]
+    fn dropping_a_transaction_without_committing_leaves_the_backend_untouched() {
+        let mut backend = InMemoryBackend::new();
+
+        let mut txn = Transaction::new();
+        txn.put(b"mmr_root".to_vec(), b"deadbeef".to_vec());
+        drop(txn);
+
+        assert_eq!(None, backend.get(b"mmr_root"));
+    }
+
+    #[test]
+    fn writes_from_multiple_logical_structures_land_in_one_transaction() {
+        let mut backend = InMemoryBackend::new();
+        let mut txn = Transaction::new();
+
+        // Simulates an archival MMR enqueuing a peak update...
+        txn.put(b"mmr/peak/0".to_vec(), b"peak-digest".to_vec());
+        // ...and its companion index vector enqueuing the matching leaf-count bump, in the same
+        // transaction, so a crash between the two writes can't happen.
+        txn.put(b"index/leaf_count".to_vec(), b"42".to_vec());
+
+        assert!(!txn.is_empty());
+        txn.commit(&mut backend);
+
+        assert_eq!(Some(b"peak-digest".to_vec()), backend.get(b"mmr/peak/0"));
+        assert_eq!(Some(b"42".to_vec()), backend.get(b"index/leaf_count"));
+    }
+}
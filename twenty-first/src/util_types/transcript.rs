@@ -0,0 +1,161 @@
+//! A Fiat–Shamir transcript built on a [`SpongeHasher`].
+//!
+//! Prover messages are absorbed under an explicit, domain-separating label, and challenges are
+//! derived from the same labeled scheme. This replaces the pattern (see
+//! [`ProofStream`](crate::util_types::proof_stream_typed::ProofStream)) of hashing the entire
+//! serialized proof stream by hand: every absorption and every challenge is bound to the label
+//! that names it, and a label may not be reused, so a malicious prover cannot bind two different
+//! things to what the verifier believes is a single challenge point.
+
+use std::collections::HashSet;
+use std::iter;
+
+use itertools::Itertools;
+
+use crate::shared_math::b_field_element::{BFieldElement, BFIELD_ONE, BFIELD_ZERO};
+use crate::shared_math::bfield_codec::BFieldCodec;
+use crate::shared_math::digest::{Digest, DIGEST_LENGTH};
+use crate::shared_math::other::roundup_nearest_multiple;
+use crate::shared_math::x_field_element::XFieldElement;
+use crate::util_types::algebraic_hasher::{AlgebraicHasher, RATE};
+
+/// A Fiat–Shamir transcript: an append-only, labeled log of prover messages from which
+/// challenges are derived.
+#[derive(Debug, Clone)]
+pub struct Transcript<H: AlgebraicHasher> {
+    sponge: H::SpongeState,
+    used_labels: HashSet<String>,
+}
+
+impl<H: AlgebraicHasher> Default for Transcript<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: AlgebraicHasher> Transcript<H> {
+    pub fn new() -> Self {
+        Self {
+            sponge: H::init(),
+            used_labels: HashSet::new(),
+        }
+    }
+
+    /// Absorb the domain-separation tag for `label`, ensuring `label` is used at most once.
+    ///
+    /// Reusing a label would let a malicious prover bind unrelated data to a position the
+    /// verifier believes is uniquely determined by that label, defeating the whole point of
+    /// domain separation. Panicking here catches that class of bug at the source rather than
+    /// producing a proof that merely fails to verify.
+    fn bind_label(&mut self, label: &str) {
+        assert!(
+            self.used_labels.insert(label.to_string()),
+            "transcript label {label:?} must not be absorbed more than once"
+        );
+        let label_elements = label
+            .bytes()
+            .map(|b| BFieldElement::new(b as u64))
+            .collect_vec();
+        let tag = H::hash_varlen(&label_elements);
+        self.absorb_elements(&tag.values());
+    }
+
+    /// Absorb `elements`, padding to a multiple of [`RATE`] the same way
+    /// [`AlgebraicHasher::hash_varlen`] does.
+    fn absorb_elements(&mut self, elements: &[BFieldElement]) {
+        let padded_length = roundup_nearest_multiple(elements.len() + 1, RATE);
+        let padding = [&BFIELD_ONE].into_iter().chain(iter::repeat(&BFIELD_ZERO));
+        let padded_input = elements.iter().chain(padding).take(padded_length);
+        H::absorb_repeatedly(&mut self.sponge, padded_input);
+    }
+
+    /// Absorb a labeled prover message.
+    pub fn absorb<T: BFieldCodec>(&mut self, label: &str, message: &T) {
+        self.bind_label(label);
+        self.absorb_elements(&message.encode());
+    }
+
+    /// Derive a labeled challenge [`Digest`].
+    pub fn challenge_digest(&mut self, label: &str) -> Digest {
+        self.bind_label(label);
+        let squeezed = H::squeeze(&mut self.sponge);
+        Digest::new((&squeezed[..DIGEST_LENGTH]).try_into().unwrap())
+    }
+
+    /// Derive a single labeled [`XFieldElement`] challenge.
+    pub fn challenge_scalar(&mut self, label: &str) -> XFieldElement {
+        self.bind_label(label);
+        H::sample_scalars(&mut self.sponge, 1)[0]
+    }
+
+    /// Derive `num_indices` labeled index challenges in `[0, upper_bound)`.
+    pub fn challenge_indices(
+        &mut self,
+        label: &str,
+        upper_bound: u32,
+        num_indices: usize,
+    ) -> Vec<u32> {
+        self.bind_label(label);
+        H::sample_indices(&mut self.sponge, upper_bound, num_indices)
+    }
+}
+
+#[cfg(test)]
+mod transcript_tests {
+    use crate::shared_math::b_field_element::BFieldElement;
+    use crate::shared_math::tip5::Tip5;
+
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "must not be absorbed more than once")]
+    fn reusing_a_label_panics() {
+        let mut transcript = Transcript::<Tip5>::new();
+        transcript.absorb("root", &BFieldElement::new(1));
+        transcript.absorb("root", &BFieldElement::new(2));
+    }
+
+    #[test]
+    fn same_absorptions_give_same_challenges() {
+        let mut a = Transcript::<Tip5>::new();
+        let mut b = Transcript::<Tip5>::new();
+
+        a.absorb("root", &BFieldElement::new(42));
+        b.absorb("root", &BFieldElement::new(42));
+
+        assert_eq!(a.challenge_digest("index"), b.challenge_digest("index"));
+    }
+
+    #[test]
+    fn different_labels_give_different_challenges() {
+        let mut a = Transcript::<Tip5>::new();
+        a.absorb("root", &BFieldElement::new(42));
+        let challenge_a = a.challenge_digest("weight");
+
+        let mut b = Transcript::<Tip5>::new();
+        b.absorb("root", &BFieldElement::new(42));
+        let challenge_b = b.challenge_digest("other-weight");
+
+        assert_ne!(challenge_a, challenge_b);
+    }
+
+    #[test]
+    fn different_messages_give_different_challenges() {
+        let mut a = Transcript::<Tip5>::new();
+        a.absorb("root", &BFieldElement::new(42));
+
+        let mut b = Transcript::<Tip5>::new();
+        b.absorb("root", &BFieldElement::new(43));
+
+        assert_ne!(a.challenge_digest("weight"), b.challenge_digest("weight"));
+    }
+
+    #[test]
+    fn challenge_indices_are_in_bounds() {
+        let mut transcript = Transcript::<Tip5>::new();
+        transcript.absorb("root", &BFieldElement::new(1));
+        let indices = transcript.challenge_indices("query_indices", 128, 20);
+        assert_eq!(20, indices.len());
+        assert!(indices.into_iter().all(|i| i < 128));
+    }
+}
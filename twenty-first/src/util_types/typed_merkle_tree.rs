@@ -0,0 +1,146 @@
+//! A [`MerkleTree`] variant committing directly to typed leaves rather than to pre-hashed
+//! [`Digest`]s. Each leaf `L: BFieldCodec` is hashed via [`AlgebraicHasher::hash`] before being
+//! handed to the underlying [`MerkleTree`], and [`TypedMerkleTree`] keeps its own copy of the
+//! leaves, so a caller no longer has to maintain a separate `Vec<L>` alongside the tree just to
+//! look leaves back up by index.
+
+use crate::shared_math::bfield_codec::BFieldCodec;
+use crate::shared_math::digest::Digest;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
+use crate::util_types::merkle_tree::AuthenticationStructure;
+use crate::util_types::merkle_tree::CpuParallel;
+use crate::util_types::merkle_tree::MerkleTree;
+use crate::util_types::merkle_tree_maker::MerkleTreeMaker;
+
+#[derive(Debug, Clone)]
+pub struct TypedMerkleTree<H, L>
+where
+    H: AlgebraicHasher,
+    L: BFieldCodec + Clone,
+{
+    leaves: Vec<L>,
+    inner: MerkleTree<H>,
+}
+
+impl<H, L> TypedMerkleTree<H, L>
+where
+    H: AlgebraicHasher,
+    L: BFieldCodec + Clone,
+{
+    /// Commit to `leaves`, hashing each one's [`BFieldCodec`] encoding via
+    /// [`AlgebraicHasher::hash`].
+    pub fn new(leaves: Vec<L>) -> Self {
+        let digests: Vec<Digest> = leaves.iter().map(H::hash).collect();
+        let inner: MerkleTree<H> = CpuParallel::from_digests(&digests);
+        Self { leaves, inner }
+    }
+
+    pub fn get_root(&self) -> Digest {
+        self.inner.get_root()
+    }
+
+    pub fn get_leaf_count(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.inner.get_height()
+    }
+
+    /// The typed leaf at `index`, exactly as supplied to [`Self::new`].
+    pub fn get_leaf(&self, index: usize) -> &L {
+        &self.leaves[index]
+    }
+
+    pub fn get_leaves(&self) -> &[L] {
+        &self.leaves
+    }
+
+    /// See [`MerkleTree::get_authentication_structure`].
+    pub fn get_authentication_structure(&self, leaf_indices: &[usize]) -> AuthenticationStructure {
+        self.inner.get_authentication_structure(leaf_indices)
+    }
+
+    /// Verify a list of typed leaves and a corresponding authentication structure against a Merkle
+    /// root. Each leaf is re-hashed via [`AlgebraicHasher::hash`] before delegating to
+    /// [`MerkleTree::verify_authentication_structure`].
+    pub fn verify_authentication_structure(
+        expected_root: Digest,
+        tree_height: usize,
+        leaf_indices: &[usize],
+        leaves: &[L],
+        authentication_structure: &AuthenticationStructure,
+    ) -> bool {
+        let digests: Vec<Digest> = leaves.iter().map(H::hash).collect();
+        MerkleTree::<H>::verify_authentication_structure(
+            expected_root,
+            tree_height,
+            leaf_indices,
+            &digests,
+            authentication_structure,
+        )
+    }
+}
+
+#[cfg(test)]
+mod typed_merkle_tree_tests {
+    use crate::shared_math::b_field_element::BFieldElement;
+    use crate::shared_math::other::random_elements;
+    use crate::shared_math::tip5::Tip5;
+
+    use super::*;
+
+    #[test]
+    fn get_leaf_reproduces_the_original_typed_leaves() {
+        let leaves: Vec<BFieldElement> = random_elements(8);
+        let tree = TypedMerkleTree::<Tip5, BFieldElement>::new(leaves.clone());
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            assert_eq!(leaf, tree.get_leaf(i));
+        }
+        assert_eq!(leaves, tree.get_leaves());
+    }
+
+    #[test]
+    fn honestly_revealed_leaves_verify() {
+        let leaves: Vec<BFieldElement> = random_elements(8);
+        let tree = TypedMerkleTree::<Tip5, BFieldElement>::new(leaves.clone());
+
+        let opened_indices = [1, 4, 6];
+        let opened_leaves = opened_indices
+            .iter()
+            .map(|&i| leaves[i])
+            .collect::<Vec<_>>();
+        let auth_structure = tree.get_authentication_structure(&opened_indices);
+
+        assert!(
+            TypedMerkleTree::<Tip5, BFieldElement>::verify_authentication_structure(
+                tree.get_root(),
+                tree.get_height(),
+                &opened_indices,
+                &opened_leaves,
+                &auth_structure,
+            )
+        );
+    }
+
+    #[test]
+    fn wrong_leaf_fails_verification() {
+        let leaves: Vec<BFieldElement> = random_elements(8);
+        let tree = TypedMerkleTree::<Tip5, BFieldElement>::new(leaves);
+
+        let opened_indices = [3];
+        let wrong_leaves: Vec<BFieldElement> = random_elements(1);
+        let auth_structure = tree.get_authentication_structure(&opened_indices);
+
+        assert!(
+            !TypedMerkleTree::<Tip5, BFieldElement>::verify_authentication_structure(
+                tree.get_root(),
+                tree.get_height(),
+                &opened_indices,
+                &wrong_leaves,
+                &auth_structure,
+            )
+        );
+    }
+}